@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use aoc_core::{
+    util::{parse_lines_with, Strictness},
+    Solution,
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 1;
+
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines_with(input, Strictness::Lenient)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
+}
+
+fn f1(inputs: &[i32]) -> i32 {
+    inputs.iter().sum()
+}
+
+fn f2(inputs: &[i32]) -> i32 {
+    let l = inputs.iter().cycle();
+    let mut seen = HashSet::new();
+    let mut cur: i32 = 0;
+    for i in l {
+        cur += i;
+        if seen.contains(&cur) {
+            break;
+        } else {
+            seen.insert(cur);
+        }
+    }
+    cur
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    fn part1() {
+        check_sample::<Day1>("sample1", Part::One, 3);
+    }
+
+    #[test]
+    fn part2() {
+        check_sample::<Day1>("sample1", Part::Two, 2);
+    }
+}
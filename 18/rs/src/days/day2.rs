@@ -1,14 +1,32 @@
-#![feature(slice_partition_dedup)]
-
 use std::collections::BTreeMap;
 use std::ops::Add;
 
-use aoc18::util::{parse, print_ans};
+use aoc_core::{
+    util::{parse_lines_with, Strictness},
+    Solution,
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 2;
+
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines_with(input, Strictness::Lenient)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
 
-fn main() -> std::io::Result<()> {
-    let inputs: Vec<String> = parse("inputs/2")?;
-    print_ans(&inputs, f1, f2);
-    Ok(())
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
 }
 
 #[derive(Debug)]
@@ -32,7 +50,7 @@ impl From<&BTreeMap<char, i32>> for TwosAndThrees {
 
 impl From<&String> for TwosAndThrees {
     fn from(string: &String) -> TwosAndThrees {
-        TwosAndThrees::from(&char_count(&string))
+        TwosAndThrees::from(&char_count(string))
     }
 }
 
@@ -74,7 +92,11 @@ fn f2(inputs: &[String]) -> String {
             })
             .collect::<Vec<String>>();
         working.sort_unstable();
-        let (_, dupes) = working.partition_dedup();
+        let dupes: Vec<&String> = working
+            .windows(2)
+            .filter(|w| w[0] == w[1])
+            .map(|w| &w[0])
+            .collect();
         if !dupes.is_empty() {
             assert_eq!(dupes.len(), 1);
             return dupes.first().unwrap().to_string();
@@ -82,3 +104,20 @@ fn f2(inputs: &[String]) -> String {
     }
     String::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    fn part1() {
+        check_sample::<Day2>("sample1", Part::One, 12);
+    }
+
+    #[test]
+    fn part2() {
+        check_sample::<Day2>("sample2", Part::Two, "fgij");
+    }
+}
@@ -1,16 +1,35 @@
 use rayon::prelude::*;
 use regex::Regex;
 
-use aoc18::util::parse;
-
-fn main() -> std::io::Result<()> {
-    let inputs: Vec<String> = parse("inputs/3")?;
-    let claims = parse_claims(&inputs);
-    let sheet = claim_sheet(&claims, 1000);
-    let a1 = f1(&sheet);
-    let a2 = f2(&claims, &sheet);
-    println!("{}\n{}", a1, a2);
-    Ok(())
+use aoc_core::{
+    util::{parse_lines_with, Strictness},
+    Solution,
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 3;
+
+    type Input = (Vec<Claim>, SqMatrix);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        let inputs: Vec<String> = parse_lines_with(input, Strictness::Lenient)?;
+        let claims = parse_claims(&inputs);
+        let sheet = claim_sheet(&claims, 1000);
+        Ok((claims, sheet))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(&input.1)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(&input.0, &input.1)
+    }
 }
 
 fn parse_claims(inputs: &[String]) -> Vec<Claim> {
@@ -21,8 +40,8 @@ fn parse_claims(inputs: &[String]) -> Vec<Claim> {
 fn claim_sheet(claims: &[Claim], size: usize) -> SqMatrix {
     let mut sheet = SqMatrix::new(0, size);
     for claim in claims {
-        for i in claim.y..(claim.y + claim.height) as usize {
-            for j in claim.x..(claim.x + claim.width) as usize {
+        for i in claim.y..(claim.y + claim.height) {
+            for j in claim.x..(claim.x + claim.width) {
                 *sheet.get_mut(i, j).unwrap() += 1;
             }
         }
@@ -36,8 +55,8 @@ fn f1(sheet: &SqMatrix) -> usize {
 
 fn f2(claims: &[Claim], sheet: &SqMatrix) -> usize {
     'main: for claim in claims {
-        for i in claim.y..(claim.y + claim.height) as usize {
-            for j in claim.x..(claim.x + claim.width) as usize {
+        for i in claim.y..(claim.y + claim.height) {
+            for j in claim.x..(claim.x + claim.width) {
                 if *sheet.get(i, j).unwrap() >= 2 {
                     continue 'main;
                 }
@@ -48,7 +67,7 @@ fn f2(claims: &[Claim], sheet: &SqMatrix) -> usize {
     0
 }
 
-struct SqMatrix {
+pub struct SqMatrix {
     mat: Vec<Vec<usize>>,
 }
 
@@ -68,7 +87,7 @@ impl SqMatrix {
     }
 }
 
-struct Claim {
+pub struct Claim {
     id: usize,
     x: usize,
     y: usize,
@@ -94,3 +113,20 @@ impl From<&String> for Claim {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    fn part1() {
+        check_sample::<Day3>("sample1", Part::One, 4);
+    }
+
+    #[test]
+    fn part2() {
+        check_sample::<Day3>("sample1", Part::Two, 3);
+    }
+}
@@ -10,19 +10,36 @@ use matches::matches;
 use rayon::prelude::*;
 use regex::Regex;
 
-use aoc18::util::parse;
+use aoc_core::{
+    util::{parse_lines_with, Strictness},
+    Solution,
+};
 
-fn main() -> std::io::Result<()> {
-    let inputs: Vec<String> = parse("inputs/4")?;
-    let guard_sleeps = to_sleeps(&inputs);
-    let a1 = f1(&guard_sleeps);
-    let a2 = f2(&guard_sleeps);
-    println!("{}\n{}", a1, a2);
-    Ok(())
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 4;
+
+    type Input = HashMap<usize, Vec<Sleep>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(to_sleeps(&parse_lines_with::<String>(input, Strictness::Lenient)?))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
 }
 
 fn f1(sleeps: &HashMap<usize, Vec<Sleep>, RandomState>) -> usize {
-    let (sleepiest_id, sleepiest_sleeps) = *sleeps
+    let (sleepiest_id, sleepiest_sleeps) = sleeps
         .iter()
         .sorted_by_key(|(_, sleeps)| sleeps.iter().map(|s| s.len().num_minutes()).sum::<i64>())
         .last()
@@ -85,7 +102,7 @@ fn sleeps_per_minute(sleeps: &[Sleep]) -> Vec<usize> {
     let mut sleep_mins = vec![0usize; 60];
     for sleep in sleeps.iter() {
         for minute in sleep.start.minute()..sleep.end.minute() {
-            sleep_mins[(minute as usize)] += 1;
+            sleep_mins[minute as usize] += 1;
         }
     }
     sleep_mins
@@ -139,7 +156,7 @@ struct Shift {
 }
 
 #[derive(Debug)]
-struct Sleep {
+pub struct Sleep {
     start: NaiveDateTime,
     end: NaiveDateTime,
 }
@@ -215,7 +232,7 @@ impl ParseRecordError {
     }
 }
 
-impl<'a> fmt::Display for ParseRecordError {
+impl fmt::Display for ParseRecordError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
@@ -225,8 +242,25 @@ impl<'a> fmt::Display for ParseRecordError {
     }
 }
 
-impl<'a> Error for ParseRecordError {
+impl Error for ParseRecordError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    fn part1() {
+        check_sample::<Day4>("sample1", Part::One, 240);
+    }
+
+    #[test]
+    fn part2() {
+        check_sample::<Day4>("sample1", Part::Two, 4455);
+    }
+}
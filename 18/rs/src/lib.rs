@@ -1,25 +1,18 @@
-pub mod util {
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
-    use std::str::FromStr;
+pub mod days;
 
-    pub fn parse<T: FromStr>(path: &str) -> std::io::Result<Vec<T>> {
-        let buf_reader = BufReader::new(File::open(path)?);
-        let line_iter = buf_reader
-            .lines()
-            .filter_map(|l| l.unwrap().parse::<T>().ok());
-        Ok(line_iter.collect())
-    }
+/// Where this year's puzzle inputs live, named by day number.
+pub const INPUTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/inputs");
 
-    pub fn print_ans<I, O1, O2, F1, F2>(inputs: &[I], f1: F1, f2: F2)
-    where
-        O1: std::fmt::Display,
-        O2: std::fmt::Display,
-        F1: Fn(&[I]) -> O1,
-        F2: Fn(&[I]) -> O2,
-    {
-        let a1 = f1(&inputs);
-        let a2 = f2(&inputs);
-        println!("{}\n{}", a1, a2);
-    }
+/// Known-good answers for this year, checked by `aoc <year> --verify`.
+pub const ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+/// Checks `S`'s answer to `part` of one of the samples in
+/// `inputs/samples/<day>/`.
+#[cfg(test)]
+fn check_sample<S: aoc_core::Solution>(
+    name: &str,
+    part: aoc_core::Part,
+    expected: impl std::fmt::Display,
+) {
+    aoc_core::sample::check::<S>(INPUTS, name, part, expected)
 }
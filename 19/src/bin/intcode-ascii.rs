@@ -0,0 +1,31 @@
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use aoc19::intcode::ascii::Terminal;
+use aoc19::intcode::parse_memory;
+
+/// Runs an Intcode program that talks in ASCII, with stdin as its input and
+/// stdout as its output.
+#[derive(Parser)]
+#[command(name = "intcode-ascii")]
+struct Cli {
+    /// File holding the program, as comma-separated numbers.
+    program: PathBuf,
+    /// Replay the commands in this file, one per line, instead of reading
+    /// stdin, and print the transcript.
+    #[arg(long)]
+    script: Option<PathBuf>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let program = parse_memory(&cli.program.to_string_lossy())?;
+    let mut terminal = Terminal::new(&program);
+    match cli.script {
+        Some(path) => print!("{}", terminal.script(&fs::read_to_string(path)?)?),
+        None => terminal.interactive()?,
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::Parser;
+
+use aoc19::intcode::asm::assemble;
+use aoc19::intcode::fast::FastComputer;
+use aoc19::intcode::jit::JitComputer;
+use aoc19::intcode::{parse_memory, Computer, Machine};
+use aoc_core::bench::{self, Stats};
+
+/// Times `Computer`, `FastComputer` and `JitComputer` on day 2's brute-force
+/// search for a noun and verb, and on a nested loop. Each day 2 attempt sets
+/// the noun and verb inside the first instruction and then runs every
+/// instruction once, so `JitComputer` pays to recompile that instruction
+/// and gets nothing back: it comes out about level with `Computer` and
+/// behind `FastComputer`. The loop runs the same few instructions over and
+/// over, which is where compiling pays off.
+#[derive(Parser)]
+#[command(name = "intcode-bench")]
+struct Cli {
+    /// Program to search. Defaults to the day 2 input.
+    program: Option<PathBuf>,
+    /// Timed runs of each workload on each backend.
+    #[arg(long, default_value_t = bench::Options::default().iterations)]
+    iterations: usize,
+    /// Untimed runs before timing.
+    #[arg(long, default_value_t = bench::Options::default().warmup)]
+    warmup: usize,
+}
+
+const GOAL: i64 = 19690720;
+
+/// Sums `i * j` for every `i` and `j` below its input.
+const LOOP: &str = "
+                in   [n]
+        outer:  add  #0, #0, [j]
+        inner:  mul  [i], [j], [t]
+                add  [sum], [t], [sum]
+                add  [j], #1, [j]
+                lt   [j], [n], [t]
+                jt   [t], #inner
+                add  [i], #1, [i]
+                lt   [i], [n], [t]
+                jt   [t], #outer
+                out  [sum]
+                halt
+        n:      data 0
+        i:      data 0
+        j:      data 0
+        t:      data 0
+        sum:    data 0
+";
+
+const LOOP_SIZE: i64 = 600;
+
+/// Tries every noun and verb until `run` gives `GOAL`.
+fn search(size: usize, mut run: impl FnMut(i64, i64) -> Option<i64>) -> Option<i64> {
+    let max = size as i64;
+    for noun in 0..max {
+        for verb in 0..max {
+            if run(noun, verb) == Some(GOAL) {
+                return Some(100 * noun + verb);
+            }
+        }
+    }
+    None
+}
+
+/// The search, running each noun and verb on a clone of `start`.
+fn searcher(program: &[i64], start: impl Machine + Clone) -> impl Fn() -> Option<i64> {
+    let size = program.len();
+    move || {
+        search(size, |noun, verb| {
+            let mut computer = start.clone();
+            computer.set(1, noun);
+            computer.set(2, verb);
+            computer.run().ok().map(|_| computer.get(0))
+        })
+    }
+}
+
+/// The loop, run on a clone of `start`.
+fn looper(start: impl Machine + Clone) -> impl Fn() -> Option<i64> {
+    move || {
+        let output = start.clone().run_on(Some(LOOP_SIZE)).ok()?;
+        output.first().copied()
+    }
+}
+
+/// A `JitComputer` with everything compiled up front, for cloning.
+fn jit(program: &[i64]) -> JitComputer {
+    let mut jit = JitComputer::new(program);
+    jit.precompile();
+    jit
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let path = cli
+        .program
+        .unwrap_or_else(|| PathBuf::from(aoc19::INPUTS).join("2"));
+    let program = parse_memory(&path.to_string_lossy())?;
+    let options = bench::Options {
+        warmup: cli.warmup,
+        iterations: cli.iterations,
+    };
+
+    compare(
+        &format!("day 2 search on {}", path.display()),
+        options,
+        [
+            &searcher(&program, Computer::new(&program)),
+            &searcher(&program, FastComputer::new(&program)),
+            &searcher(&program, jit(&program)),
+        ],
+    )?;
+    println!();
+
+    let program = assemble(LOOP)?;
+    let half = LOOP_SIZE * (LOOP_SIZE - 1) / 2;
+    let looped = looper(Computer::new(&program))();
+    if looped != Some(half * half) {
+        return Err(anyhow!("the loop gave {:?}", looped));
+    }
+    compare(
+        &format!("nested loop to {}", LOOP_SIZE),
+        options,
+        [
+            &looper(Computer::new(&program)),
+            &looper(FastComputer::new(&program)),
+            &looper(jit(&program)),
+        ],
+    )
+}
+
+const NAMES: [&str; 3] = ["Computer", "FastComputer", "JitComputer"];
+
+/// Checks each of `runs`, one per backend in `NAMES`, gives the same answer,
+/// then times them against the first.
+fn compare(
+    title: &str,
+    options: bench::Options,
+    runs: [&dyn Fn() -> Option<i64>; 3],
+) -> anyhow::Result<()> {
+    let answer = runs[0]();
+    if runs.iter().any(|run| run() != answer) {
+        return Err(anyhow!("the backends disagree on the {}", title));
+    }
+    println!("{}", title);
+    let mut stats = Vec::new();
+    for (name, run) in NAMES.iter().zip(&runs) {
+        let measured = bench::measure(options, run).map_err(|e| anyhow!(e))?;
+        report(name, &measured);
+        stats.push(measured);
+    }
+    for (name, measured) in NAMES.iter().zip(&stats).skip(1) {
+        println!(
+            "{} is {:.1}x as fast as Computer",
+            name,
+            stats[0].median.as_secs_f64() / measured.median.as_secs_f64()
+        );
+    }
+    Ok(())
+}
+
+fn report(name: &str, stats: &Stats) {
+    println!(
+        "{:<12}  min {:>12}  median {:>12}  p95 {:>12}",
+        name,
+        format!("{:?}", stats.min),
+        format!("{:?}", stats.median),
+        format!("{:?}", stats.p95),
+    );
+}
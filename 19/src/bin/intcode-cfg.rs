@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use aoc19::intcode::cfg::analyse;
+use aoc19::intcode::parse_memory;
+
+/// Prints an Intcode program's control-flow graph in Graphviz's DOT
+/// language, for piping into `dot -Tsvg`.
+#[derive(Parser)]
+#[command(name = "intcode-cfg")]
+struct Cli {
+    /// File holding the program, as comma-separated numbers.
+    program: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let program = parse_memory(&cli.program.to_string_lossy())?;
+    print!("{}", analyse(&program).to_dot());
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use aoc19::intcode::debug::{Command, Debugger};
+use aoc19::intcode::parse_memory;
+
+/// Steps through an Intcode program interactively. Type `help` at the prompt
+/// for commands; an empty line repeats the last one.
+#[derive(Parser)]
+#[command(name = "intcode-dbg")]
+struct Cli {
+    /// File holding the program, as comma-separated numbers.
+    program: PathBuf,
+    /// Values to queue as input before starting.
+    #[arg(long, value_delimiter = ',', allow_negative_numbers = true)]
+    input: Vec<i64>,
+    /// Addresses to set breakpoints on before starting.
+    #[arg(long = "break", value_delimiter = ',')]
+    breakpoints: Vec<usize>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let program = parse_memory(&cli.program.to_string_lossy())?;
+    let mut debugger = Debugger::new(program);
+    debugger.execute(&Command::Input(cli.input))?;
+    for address in cli.breakpoints {
+        debugger.execute(&Command::Break(Some(address)))?;
+    }
+    let mut editor = DefaultEditor::new()?;
+    print!("{}", debugger.execute(&"dis".parse()?)?);
+    let mut last = None;
+    loop {
+        let line = match editor.readline("(intcode) ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        let command = if line.trim().is_empty() {
+            match &last {
+                Some(command) => Ok(command),
+                None => continue,
+            }
+        } else {
+            let _ = editor.add_history_entry(line.as_str());
+            line.parse::<Command>().map(|c| &*last.insert(c))
+        };
+        match command.and_then(|c| {
+            if *c == Command::Quit {
+                return Ok(None);
+            }
+            debugger.execute(c).map(Some)
+        }) {
+            Ok(Some(out)) => print!("{}", out),
+            Ok(None) => break,
+            Err(e) => println!("error: {:#}", e),
+        }
+    }
+    Ok(())
+}
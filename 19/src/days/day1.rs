@@ -0,0 +1,61 @@
+use aoc_core::{util::parse_lines, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 1;
+
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
+}
+
+fn fuel_step(mass: &i64) -> i64 {
+    mass / 3 - 2
+}
+
+fn fuel_full(mass: &i64) -> i64 {
+    let fuel = fuel_step(mass);
+    if fuel < 0 {
+        return 0;
+    }
+    fuel + fuel_full(&fuel)
+}
+
+fn f1(inputs: &[i64]) -> i64 {
+    inputs.iter().map(fuel_step).sum()
+}
+
+fn f2(inputs: &[i64]) -> i64 {
+    inputs.iter().map(fuel_full).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    fn part1() {
+        check_sample::<Day1>("sample1", Part::One, 654);
+    }
+
+    #[test]
+    fn part2() {
+        check_sample::<Day1>("sample1", Part::Two, 966);
+    }
+}
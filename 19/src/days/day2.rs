@@ -0,0 +1,46 @@
+use crate::intcode::symbolic::{Goal, Slot, Solver};
+use crate::intcode::{memory_from_str, Computer};
+use aoc_core::Solution;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 2;
+
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        memory_from_str(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
+}
+
+fn f1(input: &[i64]) -> i64 {
+    let mut mem = Computer::new(input);
+    mem.set(1, 12);
+    mem.set(2, 2);
+    mem.run().expect("program failed");
+    mem.get(0)
+}
+
+fn f2(input: &[i64]) -> i64 {
+    const GOAL: i64 = 19690720;
+    let max = input.len() as i64 - 1;
+    let solution = Solver::new(input)
+        .symbol(Slot::Cell(1), 0..=max)
+        .and_then(|s| s.symbol(Slot::Cell(2), 0..=max))
+        .expect("the noun and verb are in memory")
+        .solve(Goal::Cell(0), GOAL)
+        .expect("never found an answer");
+    100 * solution.values[0] + solution.values[1]
+}
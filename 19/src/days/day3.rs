@@ -4,12 +4,29 @@ use std::str::FromStr;
 
 use anyhow::anyhow;
 
-use aoc19::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<Wire> = parse_wires(parse("inputs/3")?);
-    print_answers(3, &inputs, f1, f2);
-    Ok(())
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 3;
+
+    type Input = Vec<Wire>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_wires(parse_lines::<String>(input)?))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
 }
 
 fn f1(wires: &[Wire]) -> u64 {
@@ -94,9 +111,7 @@ impl<T> Grid<T> {
     fn insert_and_get(&mut self, coord: &Coord, idx: usize, v: T) -> &HashMap<usize, T> {
         match self.storage.get_mut(coord) {
             Some(m) => {
-                if !m.contains_key(&idx) {
-                    m.insert(idx, v);
-                }
+                m.entry(idx).or_insert(v);
             }
             None => {
                 let mut m = HashMap::new();
@@ -104,14 +119,14 @@ impl<T> Grid<T> {
                 self.storage.insert(*coord, m);
             }
         }
-        &self.storage.get(coord).unwrap()
+        self.storage.get(coord).unwrap()
     }
 }
 
 type Wire = Vec<Segment>;
 
 #[derive(Debug)]
-struct Segment {
+pub struct Segment {
     direction: Direction,
     distance: u64,
 }
@@ -128,7 +143,7 @@ impl FromStr for Segment {
             'D' => Down,
             'L' => Left,
             'R' => Right,
-            l @ _ => return Err(anyhow!("{} is not a direction", l)),
+            l => return Err(anyhow!("{} is not a direction", l)),
         };
         let distance: u64 = chars.as_str().parse()?;
         Ok(Segment {
@@ -145,3 +160,22 @@ enum Direction {
     Left,
     Right,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    fn part1() {
+        check_sample::<Day3>("sample1", Part::One, 6);
+        check_sample::<Day3>("sample2", Part::One, 159);
+    }
+
+    #[test]
+    fn part2() {
+        check_sample::<Day3>("sample1", Part::Two, 30);
+        check_sample::<Day3>("sample2", Part::Two, 610);
+    }
+}
@@ -1,10 +1,32 @@
-use aoc19::util::print_answers;
+use anyhow::anyhow;
+use aoc_core::Solution;
 use std::cmp::Ordering;
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<u64> = (372304..847060).collect();
-    print_answers(4, &inputs, f1, f2);
-    Ok(())
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 4;
+
+    type Input = Vec<u64>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        let (lo, hi) = input
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| anyhow!("expected a range like 123-456"))?;
+        Ok((lo.parse()?..=hi.parse()?).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
 }
 
 fn f1(range: &[u64]) -> usize {
@@ -32,7 +54,7 @@ fn pred1(n: &u64) -> bool {
         }
         prev = cur;
     }
-    return has_double && !ever_ascends;
+    has_double && !ever_ascends
 }
 
 fn pred2(n: &u64) -> bool {
@@ -69,5 +91,5 @@ fn pred2(n: &u64) -> bool {
         }
         prev = cur;
     }
-    return doubles > 0 && !ever_ascends;
+    doubles > 0 && !ever_ascends
 }
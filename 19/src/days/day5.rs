@@ -0,0 +1,38 @@
+use crate::intcode::{memory_from_str, Computer};
+use aoc_core::Solution;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 5;
+
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        memory_from_str(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+fn part1(input: &[i64]) -> i64 {
+    let mut mem = Computer::new(input);
+    let output = mem.run_on(Some(1)).expect("diagnostic program failed");
+    assert!(output.iter().take(output.len() - 2).all(|&n| n == 0));
+    *output.last().unwrap()
+}
+
+fn part2(input: &[i64]) -> i64 {
+    let mut mem = Computer::new(input);
+    let output = mem.run_on(Some(5)).expect("diagnostic program failed");
+    output[0]
+}
@@ -0,0 +1,15 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+
+use aoc_core::Registry;
+
+pub fn register(registry: &mut Registry) {
+    registry.add::<day1::Day1>();
+    registry.add::<day2::Day2>();
+    registry.add::<day3::Day3>();
+    registry.add::<day4::Day4>();
+    registry.add::<day5::Day5>();
+}
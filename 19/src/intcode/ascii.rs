@@ -0,0 +1,181 @@
+//! Talking to Intcode programs that read and write ASCII text.
+
+use std::io::{self, BufRead, Write};
+
+use super::{Computer, IntcodeError, Status};
+
+/// `text` as input values, one per byte.
+pub fn encode(text: &str) -> impl Iterator<Item = i64> + '_ {
+    text.bytes().map(i64::from)
+}
+
+/// Renders `output` as text. Values outside ASCII, like the big numbers
+/// programs give as their answers, are written out as numbers on lines of
+/// their own.
+pub fn render(output: &[i64]) -> String {
+    let mut text = String::new();
+    for &value in output {
+        if (0..128).contains(&value) {
+            text.push(value as u8 as char);
+        } else {
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str(&format!("{}\n", value));
+        }
+    }
+    text
+}
+
+/// A `Computer` running an ASCII program, fed a line at a time.
+pub struct Terminal {
+    computer: Computer,
+    halted: bool,
+}
+
+impl Terminal {
+    pub fn new(program: &[i64]) -> Terminal {
+        Terminal::from(Computer::new(program))
+    }
+
+    /// The computer behind the terminal, to poke at its memory.
+    pub fn computer(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Queues `line` and a newline as input.
+    pub fn send(&mut self, line: &str) {
+        for value in encode(line).chain(encode("\n")) {
+            self.computer.push_input(value);
+        }
+    }
+
+    /// Runs until the program halts or wants more input, rendering what it
+    /// wrote in the meantime.
+    pub fn read(&mut self) -> Result<String, IntcodeError> {
+        let mut output = Vec::new();
+        loop {
+            match self.computer.run()? {
+                Status::Halted => {
+                    self.halted = true;
+                    break;
+                }
+                Status::NeedsInput => break,
+                Status::Output(value) => output.push(value),
+            }
+        }
+        Ok(render(&output))
+    }
+
+    /// Wires the program to stdin and stdout until it halts or stdin ends.
+    pub fn interactive(&mut self) -> anyhow::Result<()> {
+        let stdin = io::stdin();
+        self.session(stdin.lock(), io::stdout(), false)
+    }
+
+    /// Feeds the program `commands` a line at a time whenever it wants
+    /// input, and returns everything that would have been on screen: its
+    /// output with the commands echoed in between. Stops when the program
+    /// halts or the commands run out.
+    pub fn script(&mut self, commands: &str) -> anyhow::Result<String> {
+        let mut transcript = Vec::new();
+        self.session(commands.as_bytes(), &mut transcript, true)?;
+        Ok(String::from_utf8(transcript)?)
+    }
+
+    fn session(
+        &mut self,
+        input: impl BufRead,
+        mut output: impl Write,
+        echo: bool,
+    ) -> anyhow::Result<()> {
+        let mut lines = input.lines();
+        loop {
+            write!(output, "{}", self.read()?)?;
+            output.flush()?;
+            if self.halted {
+                return Ok(());
+            }
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+            if echo {
+                writeln!(output, "{}", line)?;
+            }
+            self.send(&line);
+        }
+    }
+}
+
+impl From<Computer> for Terminal {
+    fn from(computer: Computer) -> Terminal {
+        Terminal {
+            computer,
+            halted: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    /// Prompts with `>`, then echoes its input until it reads a `!`, when it
+    /// outputs 1000 and halts.
+    fn echo() -> Vec<i64> {
+        assemble(
+            "
+                    out  #62
+                    out  #10
+            loop:   in   [c]
+                    eq   [c], #33, [t]
+                    jt   [t], #done
+                    out  [c]
+                    jf   #0, #loop
+            done:   out  #1000
+                    halt
+            c:      data 0
+            t:      data 0
+            ",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encoding_and_rendering() {
+        assert_eq!(encode("Hi\n").collect::<Vec<_>>(), [72, 105, 10]);
+        assert_eq!(render(&[72, 105, 10, 1000]), "Hi\n1000\n");
+        assert_eq!(render(&[72, 5000, 105]), "H\n5000\ni");
+        assert_eq!(render(&[-1, 128]), "-1\n128\n");
+    }
+
+    #[test]
+    fn scripts_capture_transcripts() {
+        let mut terminal = Terminal::new(&echo());
+        let transcript = terminal.script("ab\ncd!\nnever sent\n").unwrap();
+        assert_eq!(transcript, ">\nab\nab\ncd!\ncd\n1000\n");
+        assert!(terminal.is_halted());
+
+        let mut terminal = Terminal::new(&echo());
+        assert_eq!(terminal.script("ab").unwrap(), ">\nab\nab\n");
+        assert!(!terminal.is_halted());
+        terminal.send("!");
+        assert_eq!(terminal.read().unwrap(), "1000\n");
+    }
+
+    #[test]
+    fn errors_end_scripts() {
+        let mut terminal = Terminal::new(&[104, 62, 3, -1]);
+        let error = terminal.script("x").unwrap_err();
+        assert_eq!(
+            error.downcast::<IntcodeError>().unwrap().to_string(),
+            "negative address -1 at 2 (instruction 3)"
+        );
+    }
+}
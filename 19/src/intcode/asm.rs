@@ -0,0 +1,388 @@
+//! A text format for writing Intcode by hand, and an assembler for it.
+//!
+//! Each line holds an optional `label:`, then an instruction or a `data`
+//! directive, then an optional `; comment`. Instructions use the mnemonics
+//! and operand sigils the disassembler prints:
+//!
+//! ```text
+//!         in   [n]            ; read n
+//! loop:   out  [n]
+//!         add  [n], #-1, [n]
+//!         jt   [n], #loop     ; count down to zero
+//!         halt
+//! n:      data 0
+//! ```
+//!
+//! Anywhere a number can go, a label (optionally `+` or `-` a number) can go
+//! instead, and stands for the address it was defined at. `rb` on its own is
+//! the same as `rb+0`.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use anyhow::{anyhow, bail};
+use aoc_core::util::{at_column, ParseError};
+
+use super::{Op, ParamMode};
+
+/// Assembles `source` into a program for `Computer::new`. Errors are
+/// `ParseError`s pointing at the offending line.
+pub fn assemble(source: &str) -> anyhow::Result<Vec<i64>> {
+    let mut statements = Vec::new();
+    let mut labels = HashMap::new();
+    let mut address = 0;
+    for (i, text) in source.lines().enumerate() {
+        let statement = parse_statement(text).map_err(|e| ParseError::new(i + 1, text, e))?;
+        if let Some((label, column)) = statement.label {
+            if labels.insert(label, address).is_some() {
+                let e = at_column(column, anyhow!("label `{}` is already defined", label));
+                return Err(ParseError::new(i + 1, text, e).into());
+            }
+        }
+        address += statement.len();
+        statements.push((i + 1, text, statement));
+    }
+
+    let mut program = Vec::with_capacity(address);
+    for (line, text, statement) in statements {
+        statement
+            .encode(&labels, &mut program)
+            .map_err(|e| ParseError::new(line, text, e))?;
+    }
+    Ok(program)
+}
+
+/// One line of source.
+struct Statement<'a> {
+    label: Option<(&'a str, usize)>,
+    body: Body<'a>,
+}
+
+enum Body<'a> {
+    Empty,
+    Instruction(Op, Vec<(ParamMode, Expr<'a>)>),
+    Data(Vec<Expr<'a>>),
+}
+
+/// A number, or a label plus a number, and the column it starts at.
+struct Expr<'a> {
+    label: Option<&'a str>,
+    offset: i64,
+    column: usize,
+}
+
+impl<'a> Statement<'a> {
+    /// How many cells the statement assembles to.
+    fn len(&self) -> usize {
+        match &self.body {
+            Body::Empty => 0,
+            Body::Instruction(_, operands) => operands.len() + 1,
+            Body::Data(values) => values.len(),
+        }
+    }
+
+    fn encode(&self, labels: &HashMap<&str, usize>, program: &mut Vec<i64>) -> anyhow::Result<()> {
+        match &self.body {
+            Body::Empty => {}
+            Body::Instruction(op, operands) => {
+                let modes = operands.iter().rev().fold(0, |code, (mode, _)| {
+                    code * 10
+                        + match mode {
+                            ParamMode::Position => 0,
+                            ParamMode::Immediate => 1,
+                            ParamMode::Relative => 2,
+                        }
+                });
+                program.push((modes * 100 + op.code()) as i64);
+                for (_, expr) in operands {
+                    program.push(expr.value(labels)?);
+                }
+            }
+            Body::Data(values) => {
+                for expr in values {
+                    program.push(expr.value(labels)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Expr<'a> {
+    fn value(&self, labels: &HashMap<&str, usize>) -> anyhow::Result<i64> {
+        let base = match self.label {
+            Some(label) => match labels.get(label) {
+                Some(&address) => address as i64,
+                None => return Err(at_column(self.column, anyhow!("no label `{}`", label))),
+            },
+            None => 0,
+        };
+        base.checked_add(self.offset)
+            .ok_or_else(|| at_column(self.column, anyhow!("address out of range")))
+    }
+}
+
+fn parse_statement(text: &str) -> anyhow::Result<Statement<'_>> {
+    let code = text.split(';').next().unwrap_or_default();
+    let mut rest = code.trim_start();
+
+    let mut label = None;
+    if let Some(end) = rest.find(':') {
+        let name = rest[..end].trim_end();
+        if !is_label(name) {
+            bail!(at_column(
+                column(text, name),
+                anyhow!("bad label `{}`", name)
+            ));
+        }
+        label = Some((name, column(text, name)));
+        rest = rest[end + 1..].trim_start();
+    }
+    let rest = rest.trim_end();
+    if rest.is_empty() {
+        return Ok(Statement {
+            label,
+            body: Body::Empty,
+        });
+    }
+
+    let (mnemonic, args) = match rest.find(char::is_whitespace) {
+        Some(end) => (&rest[..end], rest[end..].trim()),
+        None => (rest, ""),
+    };
+    let args: Vec<&str> = if args.is_empty() {
+        Vec::new()
+    } else {
+        args.split(',').map(str::trim).collect()
+    };
+
+    if mnemonic == "data" {
+        if args.is_empty() {
+            bail!(at_column(column(text, mnemonic), anyhow!("no data")));
+        }
+        let values = args
+            .iter()
+            .map(|arg| parse_expr(text, arg))
+            .collect::<anyhow::Result<_>>()?;
+        return Ok(Statement {
+            label,
+            body: Body::Data(values),
+        });
+    }
+
+    let op = match Op::ALL.iter().find(|op| op.mnemonic() == mnemonic) {
+        Some(&op) => op,
+        None => bail!(at_column(
+            column(text, mnemonic),
+            anyhow!("unknown instruction `{}`", mnemonic)
+        )),
+    };
+    if args.len() != op.num_params() {
+        bail!(at_column(
+            column(text, mnemonic),
+            anyhow!(
+                "`{}` takes {} operands, not {}",
+                mnemonic,
+                op.num_params(),
+                args.len()
+            )
+        ));
+    }
+    let mut operands = Vec::with_capacity(args.len());
+    for (i, arg) in args.iter().enumerate() {
+        let operand = parse_operand(text, arg)?;
+        if op.output_param() == Some(i) && operand.0 == ParamMode::Immediate {
+            bail!(at_column(
+                column(text, arg),
+                anyhow!("can't write to an immediate operand")
+            ));
+        }
+        operands.push(operand);
+    }
+    Ok(Statement {
+        label,
+        body: Body::Instruction(op, operands),
+    })
+}
+
+/// Parses `[x]`, `#x` or `rb±x`. `arg` must be a slice of `line`.
+fn parse_operand<'a>(line: &str, arg: &'a str) -> anyhow::Result<(ParamMode, Expr<'a>)> {
+    if let Some(inner) = arg.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+        Ok((ParamMode::Position, parse_expr(line, inner.trim())?))
+    } else if let Some(value) = arg.strip_prefix('#') {
+        Ok((ParamMode::Immediate, parse_expr(line, value.trim_start())?))
+    } else if let Some(offset) = arg.strip_prefix("rb") {
+        let offset = offset.trim_start();
+        if offset.is_empty() {
+            let column = column(line, arg);
+            let expr = Expr {
+                label: None,
+                offset: 0,
+                column,
+            };
+            return Ok((ParamMode::Relative, expr));
+        }
+        let expr = match offset.strip_prefix('+') {
+            Some(offset) => parse_expr(line, offset.trim_start())?,
+            None if offset.starts_with('-') => parse_expr(line, offset)?,
+            None => bail!(at_column(column(line, offset), anyhow!("expected + or -"))),
+        };
+        Ok((ParamMode::Relative, expr))
+    } else {
+        bail!(at_column(
+            column(line, arg),
+            anyhow!("operands are `[address]`, `#value` or `rb+offset`")
+        ))
+    }
+}
+
+/// Parses `42`, `-3`, `label`, `label+2` or `label-1`. `expr` must be a
+/// slice of `line`.
+fn parse_expr<'a>(line: &str, expr: &'a str) -> anyhow::Result<Expr<'a>> {
+    let column = column(line, expr);
+    if let Ok(offset) = expr.parse() {
+        return Ok(Expr {
+            label: None,
+            offset,
+            column,
+        });
+    }
+    let (label, offset) = match expr.find(['+', '-']) {
+        Some(i) => {
+            let offset = expr[i..].replace(' ', "");
+            let offset = offset
+                .trim_start_matches('+')
+                .parse::<i64>()
+                .map_err(|e| at_column(column + i, e))?;
+            (expr[..i].trim_end(), offset)
+        }
+        None => (expr, 0),
+    };
+    if !is_label(label) {
+        bail!(at_column(column, anyhow!("expected a number or a label")));
+    }
+    Ok(Expr {
+        label: Some(label),
+        offset,
+        column,
+    })
+}
+
+fn is_label(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && s != "rb"
+        && s != "data"
+}
+
+/// The 1-based column `part` starts at within `line`, which it must be a
+/// slice of.
+fn column(line: &str, part: &str) -> usize {
+    let offset = usize::try_from(part.as_ptr() as isize - line.as_ptr() as isize).unwrap_or(0);
+    line[..offset.min(line.len())].chars().count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::{disasm, Computer};
+
+    #[test]
+    fn labels_and_data() {
+        let program = assemble(
+            "
+                in   [input]
+                jf   [input], [target]
+                add  [output], [one], [output]
+            skip:
+                out  [output]
+                halt
+            input:  data -1
+            output: data 0
+            one:    data 1
+            target: data skip
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            program,
+            [3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9]
+        );
+    }
+
+    #[test]
+    fn operand_sigils() {
+        let program = assemble(
+            "
+                in   [jump+1]       ; patches the jump's condition
+            jump: jt #-1, #skip
+                add  #0, #0, [out]
+            skip:
+                out  [out]
+                arb  #-7
+                add  rb-1, #7, rb
+                halt
+            out: data 1, out - 1, -2
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            program,
+            [3, 3, 1105, -1, 9, 1101, 0, 0, 18, 4, 18, 109, -7, 21201, -1, 7, 0, 99, 1, 17, -2]
+        );
+    }
+
+    #[test]
+    fn runs() {
+        let mut computer = Computer::new(
+            &assemble(
+                "
+                        in   [n]            ; read n
+                loop:   out  [n]
+                        add  [n], #-1, [n]
+                        jt   [n], #loop     ; count down to zero
+                        halt
+                n:      data 0
+                ",
+            )
+            .unwrap(),
+        );
+        assert_eq!(computer.run_on(Some(3)).unwrap(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn round_trips_listings() {
+        let program = [
+            1002, 4, 3, 4, 33, 21201, -1, 7, 2, 109, 19, 204, -34, 99, 0, 0,
+        ];
+        let source: String = disasm::disassemble(&program)
+            .iter()
+            .map(|line| format!("{}\n", line.item))
+            .collect();
+        assert_eq!(assemble(&source).unwrap(), program);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source| assemble(source).unwrap_err().to_string();
+        assert_eq!(
+            error("  halt\n  add [1], #2, #3"),
+            "can't write to an immediate operand\n \
+             --> <input>:2:16\n  \
+             |\n\
+             2 |   add [1], #2, #3\n  \
+             |                ^^"
+        );
+        assert!(error("jt #1, #nowhere").contains("no label `nowhere`\n --> <input>:1:9"));
+        assert!(error("a: halt\na: halt").contains("already defined\n --> <input>:2:1"));
+        assert!(error("  jump #1").contains("unknown instruction `jump`\n --> <input>:1:3"));
+        assert!(error("out [1], [2]").contains("takes 1 operands, not 2"));
+        assert!(error("out 5").contains("operands are"));
+        assert!(error("data").contains("no data"));
+        assert!(error("data 1x").contains("expected a number or a label"));
+        assert!(error("1a: halt").contains("bad label `1a`"));
+        assert!(error("halt\nx: data x+9223372036854775807")
+            .contains("address out of range\n --> <input>:2:9"));
+    }
+}
@@ -0,0 +1,404 @@
+//! What a program can do without running it: the instructions reachable from
+//! address 0, split into basic blocks, and which cells are code and which
+//! are data.
+//!
+//! Jumps are followed only where their target is an immediate. A jump that
+//! reads its target from memory gets an `Edge::Unknown`, and whatever it
+//! leads to isn't found. Likewise only position-mode operands count as
+//! reading or writing a cell, since relative-mode ones depend on the
+//! relative base at the time.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::fmt::Write as _;
+
+use super::disasm::{decode_at, Item, Line, Operand};
+use super::{Op, ParamMode};
+
+/// Where control can go after a block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Edge {
+    To(usize),
+    /// Wherever a jump with a target read from memory leads.
+    Unknown,
+}
+
+/// A run of instructions only entered at the top and only left at the
+/// bottom.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Block {
+    pub start: usize,
+    pub lines: Vec<Line>,
+    /// Empty if the block ends by halting.
+    pub edges: Vec<Edge>,
+}
+
+/// What a cell of memory is used for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Cell {
+    /// Part of a reachable instruction, or a cell control reaches that
+    /// doesn't hold one, which the program might write one into first.
+    Code,
+    /// Read or written by a reachable instruction.
+    Data,
+    /// Neither: dead code, padding, or data only reached through the relative
+    /// base.
+    Unused,
+}
+
+/// A reachable instruction writing to a cell that is also code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CodeWrite {
+    /// Where the writing instruction is.
+    pub pointer: usize,
+    /// The code cell written to.
+    pub address: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Graph {
+    /// Each block, by the address it starts at.
+    pub blocks: BTreeMap<usize, Block>,
+    /// One per cell of memory.
+    pub cells: Vec<Cell>,
+    /// Addresses control can reach that don't hold an instruction, where the
+    /// program would crash.
+    pub crashes: BTreeSet<usize>,
+    pub code_writes: Vec<CodeWrite>,
+}
+
+/// Finds everything reachable from address 0 in `memory`.
+pub fn analyse(memory: &[i64]) -> Graph {
+    let mut lines = BTreeMap::new();
+    let mut leaders = BTreeSet::new();
+    let mut crashes = BTreeSet::new();
+    leaders.insert(0);
+    let mut todo = vec![0];
+    while let Some(address) = todo.pop() {
+        if lines.contains_key(&address) || crashes.contains(&address) {
+            continue;
+        }
+        let line = decode_at(memory, address);
+        let (op, operands) = match &line.item {
+            Item::Instruction { op, operands } => (*op, operands),
+            Item::Data(_) => {
+                crashes.insert(address);
+                continue;
+            }
+        };
+        let targets: Vec<usize> = successors(op, operands, address + line.item.len())
+            .into_iter()
+            .filter_map(|edge| match edge {
+                Edge::To(target) => Some(target),
+                Edge::Unknown => None,
+            })
+            .collect();
+        if is_jump(op) {
+            leaders.extend(&targets);
+        }
+        todo.extend(targets);
+        lines.insert(address, line);
+    }
+
+    let blocks = leaders
+        .iter()
+        .filter(|leader| lines.contains_key(leader))
+        .map(|&start| (start, block(&lines, &leaders, start)))
+        .collect();
+
+    let mut cells = vec![Cell::Unused; memory.len()];
+    for line in lines.values() {
+        for address in referenced(line) {
+            if let Some(cell) = cells.get_mut(address) {
+                *cell = Cell::Data;
+            }
+        }
+    }
+    for line in lines.values() {
+        for cell in &mut cells[line.address..line.address + line.item.len()] {
+            *cell = Cell::Code;
+        }
+    }
+    for &address in &crashes {
+        if let Some(cell) = cells.get_mut(address) {
+            *cell = Cell::Code;
+        }
+    }
+
+    let code_writes = lines
+        .values()
+        .filter_map(|line| {
+            let address = written(line)?;
+            if cells.get(address) == Some(&Cell::Code) {
+                Some(CodeWrite {
+                    pointer: line.address,
+                    address,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    Graph {
+        blocks,
+        cells,
+        crashes,
+        code_writes,
+    }
+}
+
+impl Graph {
+    /// The graph in Graphviz's DOT language. Blocks that write to code are
+    /// outlined in red.
+    pub fn to_dot(&self) -> String {
+        let writers: BTreeSet<usize> = self.code_writes.iter().map(|w| w.pointer).collect();
+        let mut dot = String::new();
+        dot.push_str("digraph intcode {\n");
+        dot.push_str("    node [shape=box, fontname=monospace];\n");
+        for block in self.blocks.values() {
+            let mut label = String::new();
+            for line in &block.lines {
+                label.push_str(&escape(&line.to_string()));
+                if writers.contains(&line.address) {
+                    label.push_str("  ; writes code");
+                }
+                label.push_str("\\l");
+            }
+            let colour = if block.lines.iter().any(|l| writers.contains(&l.address)) {
+                ", color=red"
+            } else {
+                ""
+            };
+            let _ = writeln!(dot, "    b{} [label=\"{}\"{}];", block.start, label, colour);
+        }
+        for address in &self.crashes {
+            let _ = writeln!(
+                dot,
+                "    b{} [label=\"{}: crash\", shape=octagon];",
+                address, address
+            );
+        }
+        if self
+            .blocks
+            .values()
+            .any(|b| b.edges.contains(&Edge::Unknown))
+        {
+            dot.push_str("    unknown [label=\"?\", shape=circle];\n");
+        }
+        for block in self.blocks.values() {
+            for edge in &block.edges {
+                let _ = match edge {
+                    Edge::To(target) => writeln!(dot, "    b{} -> b{};", block.start, target),
+                    Edge::Unknown => {
+                        writeln!(dot, "    b{} -> unknown [style=dashed];", block.start)
+                    }
+                };
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// The block starting at `start`, running until a jump, a halt, or the
+/// start of another block.
+fn block(lines: &BTreeMap<usize, Line>, leaders: &BTreeSet<usize>, start: usize) -> Block {
+    let mut block = Block {
+        start,
+        lines: Vec::new(),
+        edges: Vec::new(),
+    };
+    let mut address = start;
+    loop {
+        let line = &lines[&address];
+        block.lines.push(line.clone());
+        let next = address + line.item.len();
+        if let Item::Instruction { op, operands } = &line.item {
+            if is_jump(*op) || *op == Op::Halt {
+                block.edges = successors(*op, operands, next);
+                return block;
+            }
+        }
+        if leaders.contains(&next) || !lines.contains_key(&next) {
+            block.edges = vec![Edge::To(next)];
+            return block;
+        }
+        address = next;
+    }
+}
+
+fn is_jump(op: Op) -> bool {
+    op == Op::JumpIfTrue || op == Op::JumpIfFalse
+}
+
+/// Where control can go after an instruction, given the address just past
+/// it. Jumps with an immediate condition only go one way.
+fn successors(op: Op, operands: &[Operand], next: usize) -> Vec<Edge> {
+    if op == Op::Halt {
+        return Vec::new();
+    }
+    if !is_jump(op) {
+        return vec![Edge::To(next)];
+    }
+    let target = match operands[1] {
+        Operand {
+            mode: ParamMode::Immediate,
+            value,
+        } => usize::try_from(value).map_or(Edge::Unknown, Edge::To),
+        _ => Edge::Unknown,
+    };
+    match operands[0] {
+        Operand {
+            mode: ParamMode::Immediate,
+            value,
+        } => {
+            if (value != 0) == (op == Op::JumpIfTrue) {
+                vec![target]
+            } else {
+                vec![Edge::To(next)]
+            }
+        }
+        _ => vec![target, Edge::To(next)],
+    }
+}
+
+/// The cells in memory an instruction's position-mode operands point at.
+fn referenced(line: &Line) -> impl Iterator<Item = usize> + '_ {
+    let operands: &[Operand] = match &line.item {
+        Item::Instruction { operands, .. } => operands,
+        Item::Data(_) => &[],
+    };
+    operands
+        .iter()
+        .filter(|o| o.mode == ParamMode::Position)
+        .filter_map(|o| usize::try_from(o.value).ok())
+}
+
+/// The cell an instruction writes to, if it's known.
+fn written(line: &Line) -> Option<usize> {
+    match &line.item {
+        Item::Instruction { op, operands } => {
+            let operand = operands[op.output_param()?];
+            if operand.mode != ParamMode::Position {
+                return None;
+            }
+            usize::try_from(operand.value).ok()
+        }
+        Item::Data(_) => None,
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    #[test]
+    fn blocks_and_edges() {
+        let program = assemble(
+            "
+                    in   [n]
+            loop:   out  [n]
+                    add  [n], #-1, [n]
+                    jt   [n], #loop
+                    jf   #0, #end
+            dead:   out  #1
+            end:    jt   [n], [n]
+                    halt
+            n:      data 0
+            ",
+        )
+        .unwrap();
+        let graph = analyse(&program);
+        let edges: Vec<(usize, Vec<Edge>)> = graph
+            .blocks
+            .values()
+            .map(|b| (b.start, b.edges.clone()))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                (0, vec![Edge::To(2)]),
+                (2, vec![Edge::To(2), Edge::To(11)]),
+                (11, vec![Edge::To(16)]),
+                (16, vec![Edge::Unknown, Edge::To(19)]),
+                (19, vec![]),
+            ]
+        );
+        let lengths: Vec<usize> = graph.blocks.values().map(|b| b.lines.len()).collect();
+        assert_eq!(lengths, [1, 3, 1, 1, 1]);
+
+        let unused: Vec<usize> = (0..program.len())
+            .filter(|&i| graph.cells[i] == Cell::Unused)
+            .collect();
+        assert_eq!(unused, [14, 15]);
+        assert_eq!(graph.cells[20], Cell::Data);
+        assert!(graph.crashes.is_empty());
+        assert!(graph.code_writes.is_empty());
+    }
+
+    #[test]
+    fn crashes_and_code_writes() {
+        // Day 2's first sample: the first instruction writes over its own
+        // output operand, and the second over the first's opcode.
+        let graph = analyse(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50]);
+        assert_eq!(
+            graph.code_writes,
+            [
+                CodeWrite {
+                    pointer: 0,
+                    address: 3
+                },
+                CodeWrite {
+                    pointer: 4,
+                    address: 0
+                },
+            ]
+        );
+        assert_eq!(
+            graph.cells[8..],
+            [Cell::Code, Cell::Data, Cell::Data, Cell::Data]
+        );
+
+        let graph = analyse(&[1105, 1, 4, 99, 42, 1101, 1, 1, 0]);
+        assert_eq!(graph.crashes.iter().copied().collect::<Vec<_>>(), [4]);
+        assert_eq!(graph.blocks[&0].edges, [Edge::To(4)]);
+        assert_eq!(graph.cells[3..5], [Cell::Unused, Cell::Code]);
+
+        // Day 5 patches an instruction before running it.
+        let graph = analyse(&[3, 7, 1, 7, 8, 6, 1100, 4, 1, 99]);
+        assert_eq!(graph.crashes.iter().copied().collect::<Vec<_>>(), [6]);
+        assert_eq!(
+            graph.code_writes,
+            [CodeWrite {
+                pointer: 2,
+                address: 6
+            }]
+        );
+        let graph = analyse(&[1101, 1, 1, 9]);
+        assert_eq!(graph.crashes.iter().copied().collect::<Vec<_>>(), [4]);
+    }
+
+    #[test]
+    fn dot() {
+        let graph = analyse(&[1, 0, 0, 3, 1005, 0, 0, 99]);
+        assert_eq!(
+            graph.to_dot(),
+            concat!(
+                "digraph intcode {\n",
+                "    node [shape=box, fontname=monospace];\n",
+                "    b0 [label=\"    0  add  [0], [0], [3]  ; writes code\\l",
+                "    4  jt   [0], #0\\l\", color=red];\n",
+                "    b7 [label=\"    7  halt\\l\"];\n",
+                "    b0 -> b0;\n",
+                "    b0 -> b7;\n",
+                "}\n",
+            )
+        );
+    }
+}
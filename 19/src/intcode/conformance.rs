@@ -0,0 +1,273 @@
+//! Checks every backend against a description of every instruction, and
+//! against each other on random programs.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use super::fast::FastComputer;
+use super::jit::JitComputer;
+use super::{Computer, ErrorKind, IntcodeError, Machine, Status, MEMORY_LIMIT};
+
+/// What an instruction does, given what its parameters resolve to: the value
+/// read, or for a parameter it writes to, the address.
+#[derive(Clone, Copy, Debug)]
+enum Effect {
+    Write(i64, i64),
+    Jump(i64),
+    Output(i64),
+    AdjustBase(i64),
+    Halt,
+    Nothing,
+}
+
+/// The value waiting when an input instruction runs.
+const INPUT: i64 = 42;
+
+/// Each opcode, its number of parameters, which of them it writes to, and
+/// what it does.
+#[allow(clippy::type_complexity)]
+const SPEC: [(i64, usize, Option<usize>, fn(&[i64]) -> Effect); 10] = [
+    (1, 3, Some(2), |p| Effect::Write(p[2], p[0] + p[1])),
+    (2, 3, Some(2), |p| Effect::Write(p[2], p[0] * p[1])),
+    (3, 1, Some(0), |p| Effect::Write(p[0], INPUT)),
+    (4, 1, None, |p| Effect::Output(p[0])),
+    (5, 2, None, |p| match p[0] {
+        0 => Effect::Nothing,
+        _ => Effect::Jump(p[1]),
+    }),
+    (6, 2, None, |p| match p[0] {
+        0 => Effect::Jump(p[1]),
+        _ => Effect::Nothing,
+    }),
+    (7, 3, Some(2), |p| Effect::Write(p[2], (p[0] < p[1]).into())),
+    (8, 3, Some(2), |p| {
+        Effect::Write(p[2], (p[0] == p[1]).into())
+    }),
+    (9, 1, None, |p| Effect::AdjustBase(p[0])),
+    (99, 0, None, |_| Effect::Halt),
+];
+
+/// The instruction under test is always `code, 10, 11, 12`, with the
+/// relative base at 20. The cells around 10 and 30 are set so that every
+/// parameter reads something different in each mode, including 0 so that
+/// jumps go both ways.
+const PARAMS: [i64; 3] = [10, 11, 12];
+const BASE: i64 = 20;
+
+fn memory(code: i64) -> Vec<i64> {
+    let mut memory = vec![99; 33];
+    memory[..4].copy_from_slice(&[code, 10, 11, 12]);
+    memory[10..13].copy_from_slice(&[0, 5, -7]);
+    memory[30..33].copy_from_slice(&[3, 0, 9]);
+    memory
+}
+
+/// A machine after running one instruction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct State {
+    memory: Vec<i64>,
+    pointer: usize,
+    relative_base: i64,
+    /// What stopped the machine, if the instruction did.
+    status: Option<Status>,
+}
+
+/// What running `code` should do, worked out from `SPEC`.
+fn expected(code: i64) -> Result<State, IntcodeError> {
+    let error = |kind| IntcodeError {
+        kind,
+        pointer: 0,
+        instruction: code,
+    };
+    let &(_, params, output, effect) = SPEC
+        .iter()
+        .find(|&&(opcode, ..)| code >= 0 && code % 100 == opcode)
+        .ok_or_else(|| error(ErrorKind::UnknownOpcode))?;
+    let modes: Vec<i64> = (0..params as u32)
+        .map(|i| code / 10_i64.pow(i + 2) % 10)
+        .collect();
+    if modes.iter().any(|&mode| mode > 2) {
+        return Err(error(ErrorKind::UnknownMode));
+    }
+    if output.map(|i| modes[i]) == Some(1) {
+        return Err(error(ErrorKind::ImmediateWrite));
+    }
+    let mut memory = memory(code);
+    let resolved: Vec<i64> = modes
+        .iter()
+        .zip(&PARAMS)
+        .enumerate()
+        .map(|(i, (&mode, &param))| match mode {
+            0 if output == Some(i) => param,
+            0 => memory[param as usize],
+            1 => param,
+            _ if output == Some(i) => BASE + param,
+            _ => memory[(BASE + param) as usize],
+        })
+        .collect();
+    let mut state = State {
+        memory: Vec::new(),
+        pointer: params + 1,
+        relative_base: BASE,
+        status: None,
+    };
+    match effect(&resolved) {
+        Effect::Write(address, value) => memory[address as usize] = value,
+        Effect::Jump(target) => state.pointer = target as usize,
+        Effect::Output(value) => state.status = Some(Status::Output(value)),
+        Effect::AdjustBase(offset) => state.relative_base += offset,
+        Effect::Halt => {
+            state.pointer = 0;
+            state.status = Some(Status::Halted);
+        }
+        Effect::Nothing => {}
+    }
+    state.memory = memory;
+    Ok(state)
+}
+
+/// What `run_for(1)` returned, as a status if the instruction stopped the
+/// machine.
+fn one_step(result: Result<Status, IntcodeError>) -> Result<Option<Status>, IntcodeError> {
+    match result {
+        Ok(status) => Ok(Some(status)),
+        Err(e) if e.kind == ErrorKind::OutOfSteps => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Runs `code` on `backend`, which takes over from a `Computer` set up to
+/// run it.
+fn on<M: Machine>(backend: fn(Computer) -> M, code: i64) -> Result<State, IntcodeError> {
+    let mut computer = Computer::new(&memory(code));
+    computer.relative_base = BASE;
+    computer.push_input(INPUT);
+    let mut machine = backend(computer);
+    let status = one_step(machine.run_for(1))?;
+    Ok(State {
+        memory: machine.memory(),
+        pointer: machine.pointer(),
+        relative_base: machine.relative_base(),
+        status,
+    })
+}
+
+#[test]
+fn every_opcode_and_mode() {
+    let mut codes: Vec<i64> = Vec::new();
+    for &(opcode, params, ..) in &SPEC {
+        // Every mode for each parameter, valid or not, and one more digit
+        // past the last parameter, which is ignored.
+        for modes in 0..4_i64.pow(params as u32 + 1) {
+            let digits = (0..=params as u32).map(|i| modes / 4_i64.pow(i) % 4 * 10_i64.pow(i));
+            codes.push(opcode + 100 * digits.sum::<i64>());
+        }
+    }
+    let opcodes: Vec<i64> = SPEC.iter().map(|s| s.0).collect();
+    codes.extend((0..100).filter(|code| !opcodes.contains(code)));
+    codes.extend(&[-1, -99, -1101, i64::MIN]);
+    assert_eq!(codes.len(), 4 * 256 + 2 * 64 + 3 * 16 + 4 + 90 + 4);
+
+    let mut errors = 0;
+    for code in codes {
+        let expected = expected(code);
+        assert_eq!(on(|c| c, code), expected, "Computer running {}", code);
+        assert_eq!(
+            on(FastComputer::from, code),
+            expected,
+            "FastComputer running {}",
+            code
+        );
+        assert_eq!(
+            on(JitComputer::from, code),
+            expected,
+            "JitComputer running {}",
+            code
+        );
+        errors += expected.is_err() as usize;
+    }
+    // Each op fails unless its parameters' modes are all 0, 1 or 2 and the
+    // one it writes to isn't 1, whatever the extra digit. Every unknown
+    // opcode fails.
+    let valid = 4 * 18 * 4 + 2 * 9 * 4 + 2 * 4 + 2 * 3 * 4 + 4;
+    assert_eq!(errors, 4 * 256 + 2 * 64 + 3 * 16 + 4 - valid + 94);
+}
+
+/// Everything `machine` does given `input`, a thousand steps at a time,
+/// until it halts, fails, runs out of input or has stopped too many times.
+fn drive(
+    mut machine: impl Machine,
+    input: &[i64],
+) -> (Vec<Result<Status, IntcodeError>>, Vec<i64>) {
+    let mut input = input.iter();
+    let mut stops = Vec::new();
+    for _ in 0..20 {
+        let result = machine.run_for(1000);
+        stops.push(result);
+        match result {
+            Ok(Status::Output(_)) => {}
+            Ok(Status::NeedsInput) => match input.next() {
+                Some(&value) => machine.push_input(value),
+                None => break,
+            },
+            Err(e) if e.kind == ErrorKind::OutOfSteps => {}
+            Ok(Status::Halted) | Err(_) => break,
+        }
+    }
+    (stops, machine.memory())
+}
+
+/// A value for a cell of a random program: mostly plausible instructions and
+/// small addresses, sometimes something extreme.
+fn random_cell(rng: &mut fastrand::Rng, len: i64) -> i64 {
+    match rng.u8(0..10) {
+        0..=4 => {
+            let (opcode, ..) = SPEC[rng.usize(..SPEC.len())];
+            let modes = (0..3).fold(0, |modes, _| {
+                let most = if rng.bool() { 2 } else { 3 };
+                modes * 10 + rng.i64(0..=most)
+            });
+            opcode + 100 * modes
+        }
+        5..=8 => rng.i64(-3..len + 3),
+        _ => *rng
+            .choice(&[i64::MIN, i64::MAX, -1 << 40, 1 << 40, MEMORY_LIMIT as i64])
+            .unwrap(),
+    }
+}
+
+#[test]
+fn random_programs() {
+    let mut rng = fastrand::Rng::with_seed(2019);
+    let mut seen = Vec::new();
+    for i in 0..2000 {
+        let len = rng.i64(1..48);
+        let program: Vec<i64> = (0..len).map(|_| random_cell(&mut rng, len)).collect();
+        let input: Vec<i64> = (0..rng.usize(0..4)).map(|_| rng.i64(-10..10)).collect();
+        let run = || {
+            // Half the time, compile what can be found up front.
+            let mut jit = JitComputer::new(&program);
+            if i % 2 == 0 {
+                jit.precompile();
+            }
+            (
+                drive(Computer::new(&program), &input),
+                drive(FastComputer::new(&program), &input),
+                drive(jit.cross_checked(), &input),
+            )
+        };
+        let (slow, fast, jit) = match panic::catch_unwind(AssertUnwindSafe(run)) {
+            Ok(runs) => runs,
+            Err(_) => panic!("{:?} with input {:?} panicked", program, input),
+        };
+        assert_eq!(fast, slow, "{:?} with input {:?}", program, input);
+        assert_eq!(jit, slow, "{:?} with input {:?}", program, input);
+        for error in slow.0.iter().filter_map(|stop| stop.err()) {
+            let kind = std::mem::discriminant(&error.kind);
+            if !seen.contains(&kind) {
+                seen.push(kind);
+            }
+        }
+    }
+    // Every way a program can fail, apart from `run_on`'s missing input.
+    assert_eq!(seen.len(), 8, "only some failures were tried");
+}
@@ -0,0 +1,457 @@
+//! The commands behind the `intcode-dbg` binary, kept here so they can be
+//! driven (and tested) without a terminal.
+
+use std::convert::TryFrom;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+
+use super::disasm;
+use super::trace::{Stop, Trace};
+use super::{Computer, ErrorKind, IntcodeError, Status, MEMORY_LIMIT};
+
+pub const HELP: &str = "\
+step [n]             run n instructions (default 1), showing each one
+continue             run until a breakpoint, watchpoint, output, input or halt
+break [addr]         set a breakpoint, or list them
+unbreak <addr>       remove a breakpoint
+watch [addr]         stop after writes to addr, or list watchpoints
+unwatch <addr>       remove a watchpoint
+get <addr> [n]       show n cells of memory (default 1)
+set <addr|pc|rb> <v> change memory, the pointer or the relative base
+input [v...]         queue input values, or show the queue
+dis [addr] [n]       disassemble n instructions (default around the pointer)
+info                 show the pointer, relative base, input and output
+history              show the last instructions run
+reset                reload the program
+help                 show this
+quit                 leave";
+
+/// How many instructions `dis` shows by default, and how many of them come
+/// before the pointer.
+const LISTING: usize = 10;
+const LISTING_BEFORE: usize = 3;
+/// The most cells `get` shows at once.
+const MAX_CELLS: usize = 1000;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Command {
+    Step(usize),
+    Continue,
+    Break(Option<usize>),
+    Unbreak(usize),
+    Watch(Option<i64>),
+    Unwatch(i64),
+    Get(i64, usize),
+    Set(Target, i64),
+    Input(Vec<i64>),
+    Disassemble(Option<usize>, usize),
+    Info,
+    History,
+    Reset,
+    Help,
+    Quit,
+}
+
+/// Something `set` can change.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Target {
+    Address(i64),
+    Pointer,
+    RelativeBase,
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Command> {
+        let mut words = s.split_whitespace();
+        let name = words.next().unwrap_or("help");
+        let args: Vec<&str> = words.collect();
+        let num = |i: usize| -> anyhow::Result<Option<i64>> {
+            args.get(i)
+                .map(|a| a.parse().with_context(|| format!("bad number `{}`", a)))
+                .transpose()
+        };
+        let addr = |i: usize| -> anyhow::Result<Option<usize>> {
+            args.get(i)
+                .map(|a| a.parse().with_context(|| format!("bad address `{}`", a)))
+                .transpose()
+        };
+        fn required<T>(name: &str, v: Option<T>) -> anyhow::Result<T> {
+            v.ok_or_else(|| anyhow!("`{}` needs an argument", name))
+        }
+
+        let command = match name {
+            "s" | "step" => Command::Step(addr(0)?.unwrap_or(1)),
+            "c" | "continue" => Command::Continue,
+            "b" | "break" => Command::Break(addr(0)?),
+            "unbreak" => Command::Unbreak(required(name, addr(0)?)?),
+            "w" | "watch" => Command::Watch(num(0)?),
+            "unwatch" => Command::Unwatch(required(name, num(0)?)?),
+            "g" | "get" => Command::Get(required(name, num(0)?)?, addr(1)?.unwrap_or(1)),
+            "set" => {
+                let target = match args.first() {
+                    Some(&"pc") => Target::Pointer,
+                    Some(&"rb") => Target::RelativeBase,
+                    _ => Target::Address(required(name, num(0)?)?),
+                };
+                Command::Set(target, required(name, num(1)?)?)
+            }
+            "i" | "input" => Command::Input(
+                (0..args.len())
+                    .map(|i| num(i).map(Option::unwrap))
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            "d" | "dis" => Command::Disassemble(addr(0)?, addr(1)?.unwrap_or(LISTING)),
+            "info" => Command::Info,
+            "history" => Command::History,
+            "reset" => Command::Reset,
+            "h" | "help" => Command::Help,
+            "q" | "quit" => Command::Quit,
+            _ => bail!("unknown command `{}`; try `help`", name),
+        };
+        Ok(command)
+    }
+}
+
+/// A program being debugged, and everything it has output so far.
+pub struct Debugger {
+    program: Vec<i64>,
+    computer: Computer,
+    trace: Trace<'static>,
+    output: Vec<i64>,
+}
+
+impl Debugger {
+    pub fn new(program: Vec<i64>) -> Debugger {
+        Debugger {
+            computer: Computer::new(&program),
+            program,
+            trace: Trace::new(64),
+            output: Vec::new(),
+        }
+    }
+
+    /// Runs `command`, returning what to show the user.
+    pub fn execute(&mut self, command: &Command) -> anyhow::Result<String> {
+        let mut out = String::new();
+        match command {
+            Command::Step(n) => {
+                for _ in 0..*n {
+                    let stop = self
+                        .computer
+                        .step_traced(&mut self.trace)
+                        .map_err(crashed)?;
+                    if let Some(step) = self.trace.history().last() {
+                        if stop != Some(Stop::Status(Status::NeedsInput)) {
+                            writeln!(out, "{}", step)?;
+                        }
+                    }
+                    match stop {
+                        // The step it stopped after is already shown.
+                        Some(Stop::Watchpoint(_)) => {
+                            writeln!(out, "watchpoint")?;
+                            break;
+                        }
+                        Some(stop) => {
+                            writeln!(out, "{}", self.describe(stop))?;
+                            break;
+                        }
+                        None => {}
+                    }
+                }
+            }
+            Command::Continue => {
+                let stop = self.computer.run_traced(&mut self.trace).map_err(crashed)?;
+                writeln!(out, "{}", self.describe(stop))?;
+            }
+            Command::Break(Some(address)) => {
+                self.trace.breakpoints.insert(*address);
+            }
+            Command::Break(None) => {
+                for address in &self.trace.breakpoints {
+                    writeln!(out, "{}", address)?;
+                }
+            }
+            Command::Unbreak(address) => {
+                if !self.trace.breakpoints.remove(address) {
+                    bail!("no breakpoint at {}", address);
+                }
+            }
+            Command::Watch(Some(address)) => {
+                self.trace.watchpoints.insert(*address);
+            }
+            Command::Watch(None) => {
+                for address in &self.trace.watchpoints {
+                    writeln!(out, "{}", address)?;
+                }
+            }
+            Command::Unwatch(address) => {
+                if !self.trace.watchpoints.remove(address) {
+                    bail!("no watchpoint at {}", address);
+                }
+            }
+            Command::Get(address, n) => {
+                checked_address(*address)?;
+                if *n > MAX_CELLS {
+                    bail!("can show at most {} cells at once", MAX_CELLS);
+                }
+                let end = (address + *n as i64).min(MEMORY_LIMIT as i64);
+                for a in *address..end {
+                    writeln!(out, "{:>5}  {}", a, self.computer.get(a))?;
+                }
+            }
+            Command::Set(Target::Address(address), value) => {
+                checked_address(*address)?;
+                self.computer.set(*address, *value);
+            }
+            Command::Set(Target::Pointer, value) => {
+                self.computer.pointer =
+                    usize::try_from(*value).map_err(|_| anyhow!("negative pointer {}", value))?;
+            }
+            Command::Set(Target::RelativeBase, value) => self.computer.relative_base = *value,
+            Command::Input(values) if values.is_empty() => {
+                writeln!(out, "{:?}", self.computer.input)?;
+            }
+            Command::Input(values) => self.computer.input.extend(values),
+            Command::Disassemble(start, n) => out = self.listing(*start, *n),
+            Command::Info => {
+                writeln!(out, "pointer:       {}", self.computer.pointer)?;
+                writeln!(out, "relative base: {}", self.computer.relative_base)?;
+                writeln!(out, "input:         {:?}", self.computer.input)?;
+                writeln!(out, "output:        {:?}", self.output)?;
+            }
+            Command::History => out = self.trace.dump(),
+            Command::Reset => {
+                self.computer = Computer::new(&self.program);
+                self.trace.clear_history();
+                self.output.clear();
+            }
+            Command::Help => writeln!(out, "{}", HELP)?,
+            Command::Quit => {}
+        }
+        Ok(out)
+    }
+
+    fn describe(&mut self, stop: Stop) -> String {
+        match stop {
+            Stop::Status(Status::Halted) => "halted".to_string(),
+            Stop::Status(Status::NeedsInput) => "waiting for input".to_string(),
+            Stop::Status(Status::Output(value)) => {
+                self.output.push(value);
+                match u8::try_from(value) {
+                    Ok(b) if b.is_ascii_graphic() || b == b' ' => {
+                        format!("output: {} ({:?})", value, b as char)
+                    }
+                    _ => format!("output: {}", value),
+                }
+            }
+            Stop::Breakpoint(address) => format!("breakpoint at {}", address),
+            Stop::Watchpoint(step) => format!("watchpoint\n{}", step),
+        }
+    }
+
+    /// `n` instructions from `start`, or if it's not given, a few before the
+    /// pointer and the rest from it, marking the pointer with `=>`.
+    fn listing(&self, start: Option<usize>, n: usize) -> String {
+        let memory = &self.computer.memory();
+        let pointer = self.computer.pointer;
+        let mut lines = Vec::new();
+        let mut address = match start {
+            Some(start) => start,
+            None => {
+                let before: Vec<_> = disasm::disassemble(memory)
+                    .into_iter()
+                    .take_while(|l| l.address + l.item.len() <= pointer)
+                    .collect();
+                let skip = before.len().saturating_sub(LISTING_BEFORE.min(n));
+                lines.extend(before.into_iter().skip(skip));
+                pointer
+            }
+        };
+        while lines.len() < n && address < memory.len() {
+            let line = disasm::decode_at(memory, address);
+            address += line.item.len().max(1);
+            lines.push(line);
+        }
+        lines
+            .iter()
+            .map(|l| {
+                let marker = if l.address == pointer { "=>" } else { "  " };
+                format!("{}{}\n", marker, l)
+            })
+            .collect()
+    }
+}
+
+fn crashed(error: IntcodeError) -> anyhow::Error {
+    anyhow!("program crashed: {}", error)
+}
+
+/// Fails on addresses the program couldn't write to either, with the error
+/// it would give.
+fn checked_address(address: i64) -> anyhow::Result<()> {
+    if address < 0 {
+        bail!(ErrorKind::NegativeAddress(address));
+    }
+    if address as u64 >= MEMORY_LIMIT as u64 {
+        bail!(ErrorKind::AddressTooLarge(address));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    fn run(debugger: &mut Debugger, command: &str) -> String {
+        debugger.execute(&command.parse().unwrap()).unwrap()
+    }
+
+    fn debugger() -> Debugger {
+        Debugger::new(
+            assemble(
+                "
+                        in   [n]
+                loop:   out  [n]
+                        add  [n], #-1, [n]
+                        jt   [n], #loop
+                        halt
+                n:      data 0
+                ",
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!("step 3".parse::<Command>().unwrap(), Command::Step(3));
+        assert_eq!("s".parse::<Command>().unwrap(), Command::Step(1));
+        assert_eq!(
+            "set rb -4".parse::<Command>().unwrap(),
+            Command::Set(Target::RelativeBase, -4)
+        );
+        assert_eq!(
+            "input 1 -2 3".parse::<Command>().unwrap(),
+            Command::Input(vec![1, -2, 3])
+        );
+        assert!("set 4".parse::<Command>().is_err());
+        assert!("get x".parse::<Command>().is_err());
+        assert!("frobnicate".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn stepping_and_breakpoints() {
+        let mut debugger = debugger();
+        assert_eq!(run(&mut debugger, "step"), "waiting for input\n");
+        run(&mut debugger, "input 2");
+        assert_eq!(run(&mut debugger, "input"), "[2]\n");
+        assert_eq!(
+            run(&mut debugger, "step 2"),
+            concat!(
+                "    0  in   [12]                    ; -> [12] = 2\n",
+                "    2  out  [12]                    ; 2\n",
+                "output: 2\n",
+            )
+        );
+        run(&mut debugger, "break 2");
+        assert_eq!(run(&mut debugger, "continue"), "breakpoint at 2\n");
+        assert_eq!(run(&mut debugger, "get 12"), "   12  1\n");
+        run(&mut debugger, "set 12 3");
+        assert_eq!(run(&mut debugger, "continue"), "output: 3\n");
+        run(&mut debugger, "unbreak 2");
+        run(&mut debugger, "continue");
+        run(&mut debugger, "continue");
+        assert_eq!(run(&mut debugger, "continue"), "halted\n");
+        assert!(run(&mut debugger, "info").contains("output:        [2, 3, 2, 1]"));
+        assert!(debugger.execute(&Command::Unbreak(2)).is_err());
+    }
+
+    #[test]
+    fn addresses_are_bounded() {
+        let mut debugger = debugger();
+        let error = |debugger: &mut Debugger, command: &str| {
+            let command = command.parse().unwrap();
+            debugger.execute(&command).unwrap_err().to_string()
+        };
+        assert_eq!(
+            error(&mut debugger, "set 100000000000000 1"),
+            "address 100000000000000 is past the memory limit"
+        );
+        assert_eq!(error(&mut debugger, "get -1"), "negative address -1");
+        let last = MEMORY_LIMIT - 1;
+        assert_eq!(
+            run(&mut debugger, &format!("get {} 100", last)),
+            format!("{:>5}  0\n", last)
+        );
+        let huge = format!("get 0 {}", usize::MAX);
+        assert_eq!(
+            error(&mut debugger, &huge),
+            "can show at most 1000 cells at once"
+        );
+        assert_eq!(run(&mut debugger, "get 0 1000").lines().count(), 1000);
+        assert_eq!(debugger.computer.memory().len(), 13);
+    }
+
+    #[test]
+    fn watchpoints() {
+        let mut debugger = debugger();
+        run(&mut debugger, "input 1");
+        run(&mut debugger, "watch 12");
+        assert_eq!(
+            run(&mut debugger, "continue"),
+            "watchpoint\n    0  in   [12]                    ; -> [12] = 1\n"
+        );
+        run(&mut debugger, "reset");
+        run(&mut debugger, "input 1");
+        assert_eq!(
+            run(&mut debugger, "step 2"),
+            "    0  in   [12]                    ; -> [12] = 1\nwatchpoint\n"
+        );
+    }
+
+    #[test]
+    fn reset_forgets_history() {
+        let mut debugger = debugger();
+        run(&mut debugger, "input 1");
+        run(&mut debugger, "step 2");
+        assert_eq!(run(&mut debugger, "history").lines().count(), 2);
+        run(&mut debugger, "reset");
+        assert_eq!(run(&mut debugger, "history"), "");
+    }
+
+    #[test]
+    fn disassembly_around_the_pointer() {
+        let mut debugger = debugger();
+        run(&mut debugger, "set pc 8");
+        assert_eq!(
+            run(&mut debugger, "dis"),
+            concat!(
+                "      0  in   [12]\n",
+                "      2  out  [12]\n",
+                "      4  add  [12], #-1, [12]\n",
+                "=>    8  jt   [12], #2\n",
+                "     11  halt\n",
+                "     12  data 0\n",
+            )
+        );
+        assert_eq!(run(&mut debugger, "dis 11 1"), "     11  halt\n");
+    }
+
+    #[test]
+    fn crashes_are_errors() {
+        let mut debugger = Debugger::new(vec![1105, 1, 100]);
+        let error = debugger.execute(&Command::Continue).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("program crashed: overran memory"));
+        assert_eq!(
+            run(&mut debugger, "info").lines().next(),
+            Some("pointer:       100")
+        );
+    }
+}
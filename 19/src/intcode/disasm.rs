@@ -0,0 +1,212 @@
+//! Turns Intcode back into something a person can read.
+//!
+//! Memory is decoded in a single sweep from address 0: every cell that starts
+//! a well-formed instruction is listed as one, and anything else is listed as
+//! `data`. Data that happens to look like an instruction will be listed as
+//! one, so the listing is a reading aid, not a proof of what runs.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::{Op, ParamMode};
+
+/// A decoded parameter: `[12]` reads address 12, `#5` is the value 5 and
+/// `rb+3` reads three past the relative base.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Operand {
+    pub mode: ParamMode,
+    pub value: i64,
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            ParamMode::Position => write!(f, "[{}]", self.value),
+            ParamMode::Immediate => write!(f, "#{}", self.value),
+            ParamMode::Relative if self.value < 0 => write!(f, "rb{}", self.value),
+            ParamMode::Relative => write!(f, "rb+{}", self.value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Item {
+    Instruction { op: Op, operands: Vec<Operand> },
+    Data(Vec<i64>),
+}
+
+impl Item {
+    /// How many cells of memory the item covers.
+    pub fn len(&self) -> usize {
+        match self {
+            Item::Instruction { operands, .. } => operands.len() + 1,
+            Item::Data(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, args): (_, Vec<String>) = match self {
+            Item::Instruction { op, operands } => (
+                op.mnemonic(),
+                operands.iter().map(Operand::to_string).collect(),
+            ),
+            Item::Data(values) => ("data", values.iter().map(i64::to_string).collect()),
+        };
+        if args.is_empty() {
+            write!(f, "{}", name)
+        } else {
+            write!(f, "{:<4} {}", name, args.join(", "))
+        }
+    }
+}
+
+/// An item and the address it starts at.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Line {
+    pub address: usize,
+    pub item: Item,
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5}  {}", self.address, self.item)
+    }
+}
+
+/// How many values go on one line of `data`.
+const DATA_PER_LINE: usize = 8;
+
+/// Decodes the cell at `address` as an instruction, or as a single cell of
+/// data if it isn't one.
+pub fn decode_at(memory: &[i64], address: usize) -> Line {
+    let item = memory
+        .get(address)
+        .and_then(|&code| {
+            let (op, modes) = decode(code)?;
+            let params = memory.get(address + 1..address + 1 + modes.len())?;
+            let operands = modes
+                .into_iter()
+                .zip(params)
+                .map(|(mode, &value)| Operand { mode, value })
+                .collect();
+            Some(Item::Instruction { op, operands })
+        })
+        .unwrap_or_else(|| Item::Data(memory.get(address).copied().into_iter().collect()));
+    Line { address, item }
+}
+
+/// Decodes all of `memory`, running neighbouring data cells together.
+pub fn disassemble(memory: &[i64]) -> Vec<Line> {
+    let mut lines: Vec<Line> = Vec::new();
+    let mut address = 0;
+    while address < memory.len() {
+        let line = decode_at(memory, address);
+        address += line.item.len();
+        if let (
+            Some(Line {
+                item: Item::Data(values),
+                ..
+            }),
+            Item::Data(more),
+        ) = (lines.last_mut(), &line.item)
+        {
+            if values.len() < DATA_PER_LINE {
+                values.extend(more);
+                continue;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// `disassemble`d memory, one line per item.
+pub fn listing(memory: &[i64]) -> String {
+    disassemble(memory)
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Like `parse_op`, but rejects anything `Computer` couldn't run: unknown
+/// opcodes and modes, more mode digits than parameters, and immediate-mode
+/// outputs.
+fn decode(code: i64) -> Option<(Op, Vec<ParamMode>)> {
+    let code = usize::try_from(code).ok()?;
+    let op = Op::from_code(code % 100)?;
+    let mut mode_code = code / 100;
+    let mut modes = Vec::with_capacity(op.num_params());
+    for _ in 0..op.num_params() {
+        modes.push(ParamMode::from_code(mode_code % 10)?);
+        mode_code /= 10;
+    }
+    if mode_code != 0 {
+        return None;
+    }
+    if let Some(i) = op.output_param() {
+        if modes[i] == ParamMode::Immediate {
+            return None;
+        }
+    }
+    Some((op, modes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operands() {
+        let memory = &[1002, 4, 3, 4, 33, 21201, -1, 7, 2, 109, 19, 204, -34, 99];
+        assert_eq!(
+            listing(memory),
+            [
+                "    0  mul  [4], #3, [4]",
+                "    4  data 33",
+                "    5  add  rb-1, #7, rb+2",
+                "    9  arb  #19",
+                "   11  out  rb-34",
+                "   13  halt\n",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn data_regions() {
+        let memory = &[
+            1105, 1, 13, 0, 0, 0, 0, 0, 0, 0, 0, 11101, 222, 104, 20, 4, 20, 99, 104,
+        ];
+        let lines = disassemble(memory);
+        let text: Vec<String> = lines.iter().map(Line::to_string).collect();
+        assert_eq!(
+            text,
+            [
+                "    0  jt   #1, #13",
+                "    3  data 0, 0, 0, 0, 0, 0, 0, 0",
+                "   11  data 11101, 222",
+                "   13  out  #20",
+                "   15  out  [20]",
+                "   17  halt",
+                "   18  data 104",
+            ]
+        );
+        // Every cell is covered exactly once.
+        let covered: usize = lines.iter().map(|l| l.item.len()).sum();
+        assert_eq!(covered, memory.len());
+    }
+
+    #[test]
+    fn decode_at_pointer() {
+        let memory = &[3, 0, 4, 0, 99];
+        assert_eq!(decode_at(memory, 2).to_string(), "    2  out  [0]");
+        assert_eq!(decode_at(memory, 1).to_string(), "    1  data 0");
+        assert_eq!(decode_at(memory, 5).item, Item::Data(vec![]));
+    }
+}
@@ -0,0 +1,336 @@
+//! A faster way to run Intcode: instructions are decoded once and kept, so
+//! running them again doesn't pay to decode them again.
+
+use std::collections::VecDeque;
+
+use super::{
+    parse_op, Computer, Cpu, ErrorKind, IntcodeError, Machine, Op, ParamMode, Status, MAX_LEN,
+};
+
+/// An instruction and its parameters, as they were when decoded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Decoded {
+    op: Op,
+    modes: [ParamMode; 3],
+    params: [i64; 3],
+}
+
+/// A `Computer` that keeps its instructions decoded. A write into an
+/// instruction, by the program or through `set`, drops it to be decoded again
+/// next time, so self-modifying programs behave just as they do on
+/// `Computer`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FastComputer {
+    memory: Vec<i64>,
+    /// The instruction starting at each address, if it has been decoded.
+    decoded: Vec<Option<Decoded>>,
+    pointer: usize,
+    relative_base: i64,
+    input: VecDeque<i64>,
+}
+
+impl FastComputer {
+    pub fn new(input: &[i64]) -> FastComputer {
+        FastComputer::from(Computer::new(input))
+    }
+
+    /// A copy of memory, up to the highest address written.
+    pub fn memory(&self) -> Vec<i64> {
+        self.memory.clone()
+    }
+
+    /// The address of the next instruction to run.
+    pub fn pointer(&self) -> usize {
+        self.pointer
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    /// Queues `value` for the program's next input instruction.
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// Runs until the program halts, outputs a value or asks for input it
+    /// hasn't been given, like `Computer::run`.
+    pub fn run(&mut self) -> Result<Status, IntcodeError> {
+        loop {
+            let instruction = match self.decoded.get(self.pointer) {
+                Some(&Some(instruction)) => instruction,
+                _ => self.decode()?,
+            };
+            if let Some(status) = self.execute(&instruction)? {
+                return Ok(status);
+            }
+        }
+    }
+
+    /// Like `run`, but gives up after executing `steps` instructions, like
+    /// `Computer::run_for`.
+    pub fn run_for(&mut self, steps: usize) -> Result<Status, IntcodeError> {
+        for _ in 0..steps {
+            let instruction = match self.decoded.get(self.pointer) {
+                Some(&Some(instruction)) => instruction,
+                _ => self.decode()?,
+            };
+            if let Some(status) = self.execute(&instruction)? {
+                return Ok(status);
+            }
+        }
+        Err(self.error(ErrorKind::OutOfSteps))
+    }
+
+    /// Runs to completion with at most one input, collecting the output.
+    pub fn run_on(&mut self, input: Option<i64>) -> Result<Vec<i64>, IntcodeError> {
+        self.input.extend(input);
+        let mut output = Vec::new();
+        loop {
+            match self.run()? {
+                Status::Halted => return Ok(output),
+                Status::NeedsInput => return Err(self.error(ErrorKind::MissingInput)),
+                Status::Output(n) => output.push(n),
+            }
+        }
+    }
+
+    /// Decodes the instruction at the pointer and remembers it.
+    fn decode(&mut self) -> Result<Decoded, IntcodeError> {
+        let pointer = self.pointer;
+        if pointer >= self.memory.len() {
+            return Err(self.error(ErrorKind::OverranMemory));
+        }
+        let instruction = self.decode_at(pointer).map_err(|kind| self.error(kind))?;
+        if pointer >= self.decoded.len() {
+            self.decoded.resize(pointer + 1, None);
+        }
+        self.decoded[pointer] = Some(instruction);
+        Ok(instruction)
+    }
+
+    fn decode_at(&self, address: usize) -> Result<Decoded, ErrorKind> {
+        let (op, modes) = parse_op(self.read(address))?;
+        let mut params = [0; 3];
+        for (i, param) in params[..op.num_params()].iter_mut().enumerate() {
+            *param = self.read(address + 1 + i);
+        }
+        Ok(Decoded { op, modes, params })
+    }
+
+    /// Runs one decoded instruction, as `Computer::step` does.
+    fn execute(&mut self, instruction: &Decoded) -> Result<Option<Status>, IntcodeError> {
+        let Decoded { op, modes, params } = *instruction;
+        let flow = super::execute(self, op, modes, params).map_err(|kind| self.error(kind))?;
+        Ok(flow.follow(&mut self.pointer, op))
+    }
+
+    fn read(&self, index: usize) -> i64 {
+        self.memory.get(index).copied().unwrap_or(0)
+    }
+
+    /// Writes `index`, forgetting any decoded instruction it's part of.
+    fn write(&mut self, index: usize, value: i64) {
+        if index >= self.memory.len() {
+            self.memory.resize(index + 1, 0);
+        }
+        self.memory[index] = value;
+        let start = index.saturating_sub(MAX_LEN - 1);
+        let end = (index + 1).min(self.decoded.len());
+        for decoded in self.decoded.get_mut(start..end).unwrap_or_default() {
+            *decoded = None;
+        }
+    }
+
+    fn error(&self, kind: ErrorKind) -> IntcodeError {
+        IntcodeError {
+            kind,
+            pointer: self.pointer,
+            instruction: self.read(self.pointer),
+        }
+    }
+
+    /// Reads `index`, which is 0 if nothing has been written there yet.
+    /// Panics if `index` is negative.
+    pub fn get(&self, index: i64) -> i64 {
+        self.read(super::address(index))
+    }
+
+    /// Writes `index`, growing memory to reach it if need be. Panics if
+    /// `index` is negative or past `MEMORY_LIMIT`.
+    pub fn set(&mut self, index: i64, value: i64) {
+        self.write(super::write_address(index), value)
+    }
+}
+
+impl Cpu for FastComputer {
+    fn cell(&self, address: usize) -> i64 {
+        self.read(address)
+    }
+
+    fn set_cell(&mut self, address: usize, value: i64) {
+        self.write(address, value)
+    }
+
+    fn base(&self) -> i64 {
+        self.relative_base
+    }
+
+    fn set_base(&mut self, base: i64) {
+        self.relative_base = base;
+    }
+
+    fn input(&mut self) -> &mut VecDeque<i64> {
+        &mut self.input
+    }
+}
+
+impl Machine for FastComputer {
+    fn push_input(&mut self, value: i64) {
+        FastComputer::push_input(self, value)
+    }
+
+    fn run(&mut self) -> Result<Status, IntcodeError> {
+        FastComputer::run(self)
+    }
+
+    fn run_for(&mut self, steps: usize) -> Result<Status, IntcodeError> {
+        FastComputer::run_for(self, steps)
+    }
+
+    fn run_on(&mut self, input: Option<i64>) -> Result<Vec<i64>, IntcodeError> {
+        FastComputer::run_on(self, input)
+    }
+
+    fn memory(&self) -> Vec<i64> {
+        FastComputer::memory(self)
+    }
+
+    fn pointer(&self) -> usize {
+        FastComputer::pointer(self)
+    }
+
+    fn relative_base(&self) -> i64 {
+        FastComputer::relative_base(self)
+    }
+
+    fn get(&self, index: i64) -> i64 {
+        FastComputer::get(self, index)
+    }
+
+    fn set(&mut self, index: i64, value: i64) {
+        FastComputer::set(self, index, value)
+    }
+}
+
+/// Carries on from wherever `computer` stopped. Every address that holds a
+/// valid instruction is decoded up front, whether or not it's code, so that
+/// clones start out with nothing left to decode.
+impl From<Computer> for FastComputer {
+    fn from(computer: Computer) -> FastComputer {
+        let mut fast = FastComputer {
+            memory: computer.memory.to_vec(),
+            decoded: Vec::new(),
+            pointer: computer.pointer,
+            relative_base: computer.relative_base,
+            input: computer.input,
+        };
+        fast.decoded = (0..fast.memory.len())
+            .map(|address| fast.decode_at(address).ok())
+            .collect();
+        fast
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    /// Runs `program` on both interpreters, checking they agree.
+    fn run_both(program: &[i64], input: &[i64]) -> Result<Vec<i64>, IntcodeError> {
+        let mut slow = Computer::new(program);
+        let mut fast = FastComputer::new(program);
+        for &n in input {
+            slow.push_input(n);
+            fast.push_input(n);
+        }
+        let output = slow.run_on(None);
+        assert_eq!(fast.run_on(None), output);
+        assert_eq!(fast.memory(), slow.memory());
+        output
+    }
+
+    #[test]
+    fn matches_computer() {
+        let larger = [
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        for &n in &[7, 8, 9] {
+            assert_eq!(run_both(&larger, &[n]).unwrap(), [999 + (n - 7)]);
+        }
+        let quine = [
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        assert_eq!(run_both(&quine, &[]).unwrap(), quine);
+        assert_eq!(
+            run_both(&[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50], &[]).unwrap(),
+            Vec::<i64>::new()
+        );
+    }
+
+    #[test]
+    fn rewritten_code_is_decoded_again() {
+        // Each time round, the loop bumps the immediate operand of its own
+        // `out`, and then turns the `jt` back to it into a `halt`.
+        let program = assemble(
+            "
+            loop:   out  #10
+                    add  [loop+1], #1, [loop+1]
+                    add  [n], #-1, [n]
+                    jt   [n], #loop
+                    add  #0, #99, [loop]
+                    jf   #0, #loop
+            n:      data 3
+            ",
+        )
+        .unwrap();
+        assert_eq!(run_both(&program, &[]).unwrap(), [10, 11, 12]);
+
+        let mut fast = FastComputer::new(&[104, 1, 99]);
+        assert_eq!(fast.run(), Ok(Status::Output(1)));
+        fast.set(1, 2);
+        fast.pointer = 0;
+        assert_eq!(fast.run(), Ok(Status::Output(2)));
+    }
+
+    #[test]
+    fn errors_match_computer() {
+        let programs: &[&[i64]] = &[
+            &[],
+            &[1, 0, 0, 0],
+            &[42],
+            &[1401, 0, 0, 0, 99],
+            &[1101, 1, 2, -1, 99],
+            &[3, 0, 99],
+            &[1105, 1, -3],
+            &[11101, 1, 1, 1, 99],
+        ];
+        for program in programs {
+            assert!(run_both(program, &[]).is_err(), "{:?}", program);
+        }
+    }
+
+    #[test]
+    fn carries_on_from_computer() {
+        let mut computer = Computer::new(&[3, 11, 3, 12, 1, 11, 12, 13, 4, 13, 99, 0, 0, 0]);
+        computer.push_input(4);
+        assert_eq!(computer.run(), Ok(Status::NeedsInput));
+        let mut fast = FastComputer::from(computer);
+        fast.push_input(5);
+        assert_eq!(fast.run(), Ok(Status::Output(9)));
+        assert_eq!(fast.run(), Ok(Status::Halted));
+    }
+}
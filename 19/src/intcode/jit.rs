@@ -0,0 +1,707 @@
+//! Intcode compiled to Rust closures.
+//!
+//! The first time the pointer reaches an address, the instructions from
+//! there up to the next jump, input, output or halt are compiled into a
+//! chain of closures with their operands already bound, and the chain is
+//! kept for next time. Clones share what's been compiled until one of them
+//! compiles something new. Once the program writes to a cell that anything
+//! was compiled from, each instruction is checked against memory before it
+//! runs, and compiled again if it has changed.
+
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use super::cfg;
+use super::disasm::Item;
+use super::memory::Memory;
+use super::{
+    destination, index, operand, parse_op, relative, Computer, ErrorKind, Flow, IntcodeError,
+    Machine, Op, ParamMode, Status, MAX_LEN, MEMORY_LIMIT,
+};
+
+/// What's left of a machine's state once the pointer is taken out, which is
+/// all a compiled instruction needs.
+#[derive(Clone)]
+struct Core {
+    memory: Vec<i64>,
+    relative_base: i64,
+    input: VecDeque<i64>,
+    /// Which cells compiled instructions were built from.
+    code: Vec<bool>,
+    /// Whether anything has written to a cell in `code`, so that compiled
+    /// instructions can no longer be trusted to match memory.
+    modified: bool,
+}
+
+impl Core {
+    fn read(&self, index: usize) -> i64 {
+        self.memory.get(index).copied().unwrap_or(0)
+    }
+
+    fn write(&mut self, index: usize, value: i64) {
+        if index >= self.memory.len() {
+            self.memory.resize(index + 1, 0);
+        }
+        self.memory[index] = value;
+        if self.code.get(index) == Some(&true) {
+            self.modified = true;
+        }
+    }
+}
+
+/// A parameter that's read, worked out as far as it can be before running.
+#[derive(Clone, Copy)]
+enum Operand {
+    Position(usize),
+    Immediate(i64),
+    Relative(i64),
+    /// A position that can't be read, which fails only once it's reached.
+    Invalid(ErrorKind),
+}
+
+impl Operand {
+    fn new(mode: ParamMode, param: i64) -> Operand {
+        match mode {
+            ParamMode::Position => match index(param) {
+                Ok(index) => Operand::Position(index),
+                Err(kind) => Operand::Invalid(kind),
+            },
+            ParamMode::Immediate => Operand::Immediate(param),
+            ParamMode::Relative => Operand::Relative(param),
+        }
+    }
+
+    fn read(self, core: &Core) -> Result<i64, ErrorKind> {
+        match self {
+            Operand::Position(index) => Ok(core.read(index)),
+            Operand::Immediate(value) => Ok(value),
+            Operand::Relative(offset) => {
+                operand(ParamMode::Relative, offset, core.relative_base, |address| {
+                    core.read(address)
+                })
+            }
+            Operand::Invalid(kind) => Err(kind),
+        }
+    }
+}
+
+/// A parameter that's written to, likewise.
+#[derive(Clone, Copy)]
+enum Destination {
+    Position(usize),
+    Relative(i64),
+    /// Somewhere that can't be written, which fails only once there's a
+    /// value to write, as it does on `Computer`.
+    Invalid(ErrorKind),
+}
+
+impl Destination {
+    fn new(mode: ParamMode, param: i64) -> Destination {
+        match mode {
+            ParamMode::Relative => Destination::Relative(param),
+            _ => match destination(mode, param, 0) {
+                Ok(index) => Destination::Position(index),
+                Err(kind) => Destination::Invalid(kind),
+            },
+        }
+    }
+
+    fn write(self, core: &mut Core, value: i64) -> Result<(), ErrorKind> {
+        let index = match self {
+            Destination::Position(index) => index,
+            Destination::Relative(offset) => {
+                destination(ParamMode::Relative, offset, core.relative_base)?
+            }
+            Destination::Invalid(kind) => return Err(kind),
+        };
+        core.write(index, value);
+        Ok(())
+    }
+}
+
+type Run = Rc<dyn Fn(&mut Core) -> Result<Flow, ErrorKind>>;
+
+#[derive(Clone)]
+struct Instruction {
+    address: usize,
+    /// The cells it was compiled from, up to `next`.
+    source: [i64; MAX_LEN],
+    /// The address just past the instruction.
+    next: usize,
+    run: Run,
+}
+
+impl Instruction {
+    /// Whether memory still holds what the instruction was compiled from.
+    fn matches(&self, core: &Core) -> bool {
+        (self.address..self.next)
+            .zip(&self.source)
+            .all(|(address, &value)| core.read(address) == value)
+    }
+}
+
+/// Instructions that run one after the other, ending at the first one that
+/// can jump or stop the machine.
+#[derive(Clone)]
+struct Block {
+    instructions: Vec<Instruction>,
+}
+
+/// Compiles the instruction at `address`, returning it, its length, and
+/// whether it ends a block. Anything that can't run compiles to its error.
+fn compile_at(memory: &[i64], address: usize) -> (Run, usize, bool) {
+    let fail = |kind: ErrorKind| -> (Run, usize, bool) { (Rc::new(move |_| Err(kind)), 1, true) };
+    let code = match memory.get(address) {
+        Some(&code) => code,
+        // Writes further on can grow memory past here before it runs, and it
+        // can't have been written itself, or there'd be nothing compiled.
+        None => {
+            let run: Run = Rc::new(move |core| match address < core.memory.len() {
+                true => Err(ErrorKind::UnknownOpcode),
+                false => Err(ErrorKind::OverranMemory),
+            });
+            return (run, 1, true);
+        }
+    };
+    let (op, modes) = match parse_op(code) {
+        Ok(decoded) => decoded,
+        Err(kind) => return fail(kind),
+    };
+    let param = |i: usize| memory.get(address + 1 + i).copied().unwrap_or(0);
+    let read = |i: usize| Operand::new(modes[i], param(i));
+    let write = |i: usize| Destination::new(modes[i], param(i));
+    let run: Run = match op {
+        Op::Add => arithmetic(read(0), read(1), write(2), i64::checked_add),
+        Op::Multiply => arithmetic(read(0), read(1), write(2), i64::checked_mul),
+        Op::LessThan => arithmetic(read(0), read(1), write(2), |x, y| Some((x < y).into())),
+        Op::Equals => arithmetic(read(0), read(1), write(2), |x, y| Some((x == y).into())),
+        Op::Save => {
+            let to = write(0);
+            Rc::new(move |core| match core.input.front() {
+                Some(&value) => {
+                    to.write(core, value)?;
+                    core.input.pop_front();
+                    Ok(Flow::Next)
+                }
+                None => Ok(Flow::Stop(Status::NeedsInput)),
+            })
+        }
+        Op::Return => {
+            let x = read(0);
+            Rc::new(move |core| Ok(Flow::Output(x.read(core)?)))
+        }
+        Op::JumpIfTrue | Op::JumpIfFalse => {
+            let (condition, target) = (read(0), read(1));
+            let when = op == Op::JumpIfTrue;
+            Rc::new(move |core| {
+                if (condition.read(core)? != 0) != when {
+                    return Ok(Flow::Next);
+                }
+                Ok(Flow::Jump(index(target.read(core)?)?))
+            })
+        }
+        Op::AdjustBase => {
+            let x = read(0);
+            Rc::new(move |core| {
+                core.relative_base = relative(core.relative_base, x.read(core)?)?;
+                Ok(Flow::Next)
+            })
+        }
+        Op::Halt => Rc::new(|_| Ok(Flow::Stop(Status::Halted))),
+    };
+    let last = matches!(
+        op,
+        Op::Save | Op::Return | Op::JumpIfTrue | Op::JumpIfFalse | Op::Halt
+    );
+    (run, op.num_params() + 1, last)
+}
+
+/// An instruction that writes `apply` of its first two parameters to its
+/// third, failing if `apply` overflows.
+fn arithmetic(
+    x: Operand,
+    y: Operand,
+    to: Destination,
+    apply: impl Fn(i64, i64) -> Option<i64> + 'static,
+) -> Run {
+    Rc::new(move |core| {
+        let value = apply(x.read(core)?, y.read(core)?).ok_or(ErrorKind::Overflow)?;
+        to.write(core, value)?;
+        Ok(Flow::Next)
+    })
+}
+
+/// An Intcode machine that runs compiled code, with the same interface as
+/// `Computer`.
+#[derive(Clone)]
+pub struct JitComputer {
+    core: Core,
+    pointer: usize,
+    /// The block starting at each address, once it's been run.
+    blocks: Rc<Vec<Option<Rc<Block>>>>,
+    /// A `Computer` run alongside to check against, if cross-checking.
+    check: Option<Computer>,
+}
+
+impl JitComputer {
+    pub fn new(input: &[i64]) -> JitComputer {
+        JitComputer::from(Computer::new(input))
+    }
+
+    /// Also runs a `Computer` alongside, panicking if the two ever differ
+    /// in memory, pointer, relative base or status at the end of a block.
+    pub fn cross_checked(mut self) -> JitComputer {
+        self.check = Some(self.to_computer());
+        self
+    }
+
+    /// Compiles every block `cfg::analyse` finds reachable from address 0,
+    /// rather than waiting for the program to get to them, so that clones
+    /// made afterwards all start with them compiled.
+    pub fn precompile(&mut self) {
+        let graph = cfg::analyse(&self.core.memory);
+        for block in graph.blocks.values() {
+            self.block_at(block.start);
+            // Blocks here also end at inputs and outputs.
+            for line in &block.lines {
+                if let Item::Instruction {
+                    op: Op::Save | Op::Return,
+                    ..
+                } = line.item
+                {
+                    self.block_at(line.address + line.item.len());
+                }
+            }
+        }
+    }
+
+    /// A copy of memory, up to the highest address written.
+    pub fn memory(&self) -> Vec<i64> {
+        self.core.memory.clone()
+    }
+
+    /// The address of the next instruction to run.
+    pub fn pointer(&self) -> usize {
+        self.pointer
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.core.relative_base
+    }
+
+    /// Queues `value` for the program's next input instruction.
+    pub fn push_input(&mut self, value: i64) {
+        self.core.input.push_back(value);
+        if let Some(check) = &mut self.check {
+            check.push_input(value);
+        }
+    }
+
+    /// Runs until the program halts, outputs a value or asks for input it
+    /// hasn't been given, like `Computer::run`.
+    pub fn run(&mut self) -> Result<Status, IntcodeError> {
+        self.run_within(None)
+    }
+
+    /// Like `run`, but gives up after executing `steps` instructions, like
+    /// `Computer::run_for`.
+    pub fn run_for(&mut self, steps: usize) -> Result<Status, IntcodeError> {
+        self.run_within(Some(steps))
+    }
+
+    /// Runs to completion with at most one input, collecting the output.
+    pub fn run_on(&mut self, input: Option<i64>) -> Result<Vec<i64>, IntcodeError> {
+        if let Some(value) = input {
+            self.push_input(value);
+        }
+        let mut output = Vec::new();
+        loop {
+            match self.run()? {
+                Status::Halted => return Ok(output),
+                Status::NeedsInput => return Err(self.error(ErrorKind::MissingInput)),
+                Status::Output(n) => output.push(n),
+            }
+        }
+    }
+
+    fn run_within(&mut self, mut steps: Option<usize>) -> Result<Status, IntcodeError> {
+        loop {
+            if steps == Some(0) {
+                return Err(self.error(ErrorKind::OutOfSteps));
+            }
+            let (ran, result) = self.run_block(steps.unwrap_or(usize::MAX));
+            steps = steps.map(|steps| steps - ran);
+            self.cross_check(ran, &result);
+            if let Some(status) = result? {
+                return Ok(status);
+            }
+        }
+    }
+
+    /// Runs the block at the pointer, or its first `steps` instructions.
+    /// Returns how many instructions ran without stopping the machine, and
+    /// the status if one did stop it.
+    fn run_block(&mut self, steps: usize) -> (usize, Result<Option<Status>, IntcodeError>) {
+        let start = self.pointer;
+        let mut block = self.block_at(start);
+        let mut ran = 0;
+        while ran < steps {
+            let instruction = match block.instructions.get(ran) {
+                Some(instruction) => instruction,
+                None => break,
+            };
+            if self.core.modified && !instruction.matches(&self.core) {
+                block = self.recompile(start, block, ran);
+                continue;
+            }
+            let flow = match (instruction.run)(&mut self.core) {
+                Ok(flow) => flow,
+                Err(kind) => return (ran, Err(self.error(kind))),
+            };
+            match flow {
+                Flow::Next => self.pointer = instruction.next,
+                Flow::Jump(target) => self.pointer = target,
+                Flow::Output(value) => {
+                    self.pointer = instruction.next;
+                    return (ran, Ok(Some(Status::Output(value))));
+                }
+                Flow::Stop(status) => return (ran, Ok(Some(status))),
+            }
+            ran += 1;
+        }
+        (ran, Ok(None))
+    }
+
+    /// The block starting at `address`, compiling it if it hasn't been.
+    fn block_at(&mut self, address: usize) -> Rc<Block> {
+        if let Some(Some(block)) = self.blocks.get(address) {
+            return Rc::clone(block);
+        }
+        let block = Rc::new(self.compile(address));
+        self.store(address, Rc::clone(&block));
+        block
+    }
+
+    /// Keeps `block` as the one starting at `address`.
+    fn store(&mut self, address: usize, block: Rc<Block>) {
+        // Past the memory limit there can only be a crash, so there's no need
+        // to keep it.
+        if address < MEMORY_LIMIT {
+            let blocks = Rc::make_mut(&mut self.blocks);
+            if blocks.len() <= address {
+                blocks.resize(address + 1, None);
+            }
+            blocks[address] = Some(block);
+        }
+    }
+
+    fn compile(&mut self, start: usize) -> Block {
+        let mut instructions = Vec::new();
+        let mut address = start;
+        loop {
+            let (instruction, last) = self.compile_instruction(address);
+            address = instruction.next;
+            instructions.push(instruction);
+            if last {
+                return Block { instructions };
+            }
+        }
+    }
+
+    /// Compiles instruction `i` of `block`, which starts at `start`, again
+    /// from memory. If it's changed length or now ends the block, the rest of
+    /// the block is dropped, to be compiled afresh when the pointer gets
+    /// there.
+    fn recompile(&mut self, start: usize, mut block: Rc<Block>, i: usize) -> Rc<Block> {
+        // Let go of the stored copy so as not to copy the block needlessly.
+        if let Some(stored) = Rc::make_mut(&mut self.blocks).get_mut(start) {
+            *stored = None;
+        }
+        let (instruction, last) = self.compile_instruction(block.instructions[i].address);
+        let instructions = &mut Rc::make_mut(&mut block).instructions;
+        if last || instruction.next != instructions[i].next {
+            instructions.truncate(i + 1);
+        }
+        instructions[i] = instruction;
+        self.store(start, Rc::clone(&block));
+        block
+    }
+
+    /// Compiles the instruction at `address`, noting which cells it came
+    /// from. Returns it and whether it ends a block.
+    fn compile_instruction(&mut self, address: usize) -> (Instruction, bool) {
+        let (run, len, last) = compile_at(&self.core.memory, address);
+        let next = address + len;
+        let mut source = [0; MAX_LEN];
+        for (i, cell) in source[..len].iter_mut().enumerate() {
+            *cell = self.core.read(address + i);
+        }
+        // Only cells the program could write need marking.
+        let marked = address.min(MEMORY_LIMIT)..next.min(MEMORY_LIMIT);
+        if self.core.code.len() < marked.end {
+            self.core.code.resize(marked.end, false);
+        }
+        for cell in &mut self.core.code[marked] {
+            *cell = true;
+        }
+        let instruction = Instruction {
+            address,
+            source,
+            next,
+            run,
+        };
+        (instruction, last)
+    }
+
+    /// Runs the checking computer as far as the last block went, and checks
+    /// it ended up in the same place.
+    fn cross_check(&mut self, ran: usize, result: &Result<Option<Status>, IntcodeError>) {
+        let mut check = match self.check.take() {
+            Some(check) => check,
+            None => return,
+        };
+        // A block that carried on ran `ran` instructions; one that stopped
+        // the machine stopped in the middle of the next.
+        let expected = match result {
+            Ok(None) => match check.run_for(ran) {
+                Err(e) if e.kind == ErrorKind::OutOfSteps => Ok(None),
+                other => other.map(Some),
+            },
+            _ => check.run_for(ran + 1).map(Some),
+        };
+        let registers = (self.pointer, self.core.relative_base, *result);
+        let expected = (check.pointer(), check.relative_base(), expected);
+        if !check.memory.eq_slice(&self.core.memory) || registers != expected {
+            // Only copy memory out to show what went wrong.
+            assert_eq!(
+                (self.memory(), registers),
+                (check.memory(), expected),
+                "JitComputer and Computer differ"
+            );
+        }
+        self.check = Some(check);
+    }
+
+    fn error(&self, kind: ErrorKind) -> IntcodeError {
+        IntcodeError {
+            kind,
+            pointer: self.pointer,
+            instruction: self.core.read(self.pointer),
+        }
+    }
+
+    fn to_computer(&self) -> Computer {
+        Computer {
+            memory: Memory::new(&self.core.memory),
+            pointer: self.pointer,
+            relative_base: self.core.relative_base,
+            input: self.core.input.clone(),
+        }
+    }
+
+    /// Reads `index`, which is 0 if nothing has been written there yet.
+    /// Panics if `index` is negative.
+    pub fn get(&self, index: i64) -> i64 {
+        self.core.read(super::address(index))
+    }
+
+    /// Writes `index`, growing memory to reach it if need be. Panics if
+    /// `index` is negative or past `MEMORY_LIMIT`. Compiled code written to
+    /// is compiled again before it next runs.
+    pub fn set(&mut self, index: i64, value: i64) {
+        self.core.write(super::write_address(index), value);
+        if let Some(check) = &mut self.check {
+            check.set(index, value);
+        }
+    }
+}
+
+impl Machine for JitComputer {
+    fn push_input(&mut self, value: i64) {
+        JitComputer::push_input(self, value)
+    }
+
+    fn run(&mut self) -> Result<Status, IntcodeError> {
+        JitComputer::run(self)
+    }
+
+    fn run_for(&mut self, steps: usize) -> Result<Status, IntcodeError> {
+        JitComputer::run_for(self, steps)
+    }
+
+    fn run_on(&mut self, input: Option<i64>) -> Result<Vec<i64>, IntcodeError> {
+        JitComputer::run_on(self, input)
+    }
+
+    fn memory(&self) -> Vec<i64> {
+        JitComputer::memory(self)
+    }
+
+    fn pointer(&self) -> usize {
+        JitComputer::pointer(self)
+    }
+
+    fn relative_base(&self) -> i64 {
+        JitComputer::relative_base(self)
+    }
+
+    fn get(&self, index: i64) -> i64 {
+        JitComputer::get(self, index)
+    }
+
+    fn set(&mut self, index: i64, value: i64) {
+        JitComputer::set(self, index, value)
+    }
+}
+
+/// Carries on from wherever `computer` stopped.
+impl From<Computer> for JitComputer {
+    fn from(computer: Computer) -> JitComputer {
+        JitComputer {
+            core: Core {
+                memory: computer.memory.to_vec(),
+                relative_base: computer.relative_base,
+                input: computer.input,
+                code: Vec::new(),
+                modified: false,
+            },
+            pointer: computer.pointer,
+            blocks: Rc::default(),
+            check: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    /// Runs `program` cross-checked against a `Computer`, returning its
+    /// output and whether it wrote to its own code.
+    fn run_checked(program: &[i64], input: &[i64]) -> (Result<Vec<i64>, IntcodeError>, bool) {
+        let mut jit = JitComputer::new(program).cross_checked();
+        let mut computer = Computer::new(program);
+        for &n in input {
+            jit.push_input(n);
+            computer.push_input(n);
+        }
+        let output = jit.run_on(None);
+        assert_eq!(output, computer.run_on(None));
+        (output, jit.core.modified)
+    }
+
+    #[test]
+    fn matches_computer() {
+        let larger = [
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        for &n in &[7, 8, 9] {
+            assert_eq!(run_checked(&larger, &[n]), (Ok(vec![999 + (n - 7)]), false));
+        }
+        let quine = [
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        assert_eq!(run_checked(&quine, &[]), (Ok(quine.to_vec()), false));
+    }
+
+    #[test]
+    fn recompiles_modified_code() {
+        // Writes 99 over its own operand, which is then run as a halt.
+        let (output, modified) = run_checked(&[1002, 4, 3, 4, 33], &[]);
+        assert_eq!(output, Ok(vec![]));
+        assert!(modified);
+
+        let program = assemble(
+            "
+            loop:   out  #10
+                    add  [loop+1], #1, [loop+1]
+                    add  [n], #-1, [n]
+                    jt   [n], #loop
+                    halt
+            n:      data 3
+            ",
+        )
+        .unwrap();
+        let (output, modified) = run_checked(&program, &[]);
+        assert_eq!(output, Ok(vec![10, 11, 12]));
+        assert!(modified);
+
+        // Turns the add into an output, which is shorter and ends the block.
+        let program = assemble(
+            "
+                    add  #104, #0, [patch]
+            patch:  add  #7, #99, [0]     ; becomes out #7, halt
+                    halt
+            ",
+        )
+        .unwrap();
+        let mut jit = JitComputer::new(&program).cross_checked();
+        jit.precompile();
+        assert_eq!(jit.run_on(None), Ok(vec![7]));
+        let patched = &jit.blocks[0].as_ref().unwrap().instructions;
+        assert_eq!(patched.len(), 2);
+
+        // Writing data, even next to code, doesn't count.
+        let (output, modified) = run_checked(&[1101, 2, 3, 5, 99, 0], &[]);
+        assert_eq!(output, Ok(vec![]));
+        assert!(!modified);
+    }
+
+    #[test]
+    fn setting_code_recompiles() {
+        let mut jit = JitComputer::new(&[104, 1, 1105, 1, 0]).cross_checked();
+        assert_eq!(jit.run(), Ok(Status::Output(1)));
+        jit.set(1, 2);
+        assert_eq!(jit.run(), Ok(Status::Output(2)));
+    }
+
+    #[test]
+    fn clones_share_compiled_code() {
+        let blocks = |jit: &JitComputer| Rc::clone(&jit.blocks);
+        let quine = [
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        let mut jit = JitComputer::new(&quine);
+        jit.precompile();
+        let compiled = blocks(&jit);
+        assert_eq!(compiled.iter().flatten().count(), 3);
+
+        let mut clone = jit.clone().cross_checked();
+        assert_eq!(clone.run_on(None), Ok(quine.to_vec()));
+        assert!(Rc::ptr_eq(&blocks(&clone), &compiled));
+
+        // Compiling something new copies the blocks, leaving the rest alone.
+        let mut clone = jit.clone();
+        clone.set(0, 99);
+        assert_eq!(clone.run(), Ok(Status::Halted));
+        assert!(!Rc::ptr_eq(&blocks(&clone), &compiled));
+        assert!(Rc::ptr_eq(&blocks(&jit), &compiled));
+        assert_eq!(jit.run(), Ok(Status::Output(109)));
+    }
+
+    #[test]
+    fn counts_steps() {
+        // Loops forever, adding 1 + 1 into cell 7.
+        let mut jit = JitComputer::new(&[1101, 1, 1, 7, 1105, 1, 0, 0]).cross_checked();
+        let error = jit.run_for(5).unwrap_err();
+        assert_eq!((error.kind, error.pointer), (ErrorKind::OutOfSteps, 4));
+        let error = jit.run_for(1).unwrap_err();
+        assert_eq!((error.kind, error.pointer), (ErrorKind::OutOfSteps, 0));
+        assert_eq!(jit.run_for(0).unwrap_err().pointer, 0);
+        assert_eq!(jit.memory()[7], 2);
+    }
+
+    #[test]
+    fn carries_on_from_computer() {
+        let mut computer = Computer::new(&[3, 11, 3, 12, 1, 11, 12, 13, 4, 13, 99, 0, 0, 0]);
+        computer.push_input(4);
+        assert_eq!(computer.run(), Ok(Status::NeedsInput));
+        let mut jit = JitComputer::from(computer).cross_checked();
+        jit.push_input(5);
+        assert_eq!(jit.run(), Ok(Status::Output(9)));
+        assert_eq!(jit.run(), Ok(Status::Halted));
+    }
+}
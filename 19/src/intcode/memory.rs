@@ -0,0 +1,130 @@
+//! Intcode memory, split into pages that copies of a `Computer` share until
+//! one of them writes to a page.
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Cells per page: the most a write to shared memory has to copy.
+const PAGE: usize = 64;
+
+/// Memory that reads 0 everywhere it hasn't been written, and is cheap to
+/// clone.
+#[derive(Clone, Default)]
+pub struct Memory {
+    pages: Vec<Arc<[i64; PAGE]>>,
+    /// One past the highest address ever written.
+    len: usize,
+}
+
+impl Memory {
+    pub fn new(values: &[i64]) -> Memory {
+        let pages = values
+            .chunks(PAGE)
+            .map(|chunk| {
+                let mut page = [0; PAGE];
+                page[..chunk.len()].copy_from_slice(chunk);
+                Arc::new(page)
+            })
+            .collect();
+        Memory {
+            pages,
+            len: values.len(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn get(&self, index: usize) -> i64 {
+        match self.pages.get(index / PAGE) {
+            Some(page) => page[index % PAGE],
+            None => 0,
+        }
+    }
+
+    /// Writes `index`, first copying its page if another `Memory` shares it.
+    pub fn set(&mut self, index: usize, value: i64) {
+        let page = index / PAGE;
+        if page >= self.pages.len() {
+            // New pages all share one empty page until they're written.
+            self.pages.resize(page + 1, Arc::new([0; PAGE]));
+        }
+        Arc::make_mut(&mut self.pages[page])[index % PAGE] = value;
+        self.len = self.len.max(index + 1);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = i64> + '_ {
+        self.pages
+            .iter()
+            .flat_map(|p| p.iter().copied())
+            .take(self.len)
+    }
+
+    pub fn to_vec(&self) -> Vec<i64> {
+        self.iter().collect()
+    }
+
+    /// Whether this holds exactly `values`, without copying anything.
+    pub fn eq_slice(&self, values: &[i64]) -> bool {
+        self.len == values.len()
+            && values
+                .chunks(PAGE)
+                .zip(&self.pages)
+                .all(|(chunk, page)| chunk == &page[..chunk.len()])
+    }
+}
+
+impl PartialEq for Memory {
+    fn eq(&self, other: &Memory) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for Memory {}
+
+impl fmt::Debug for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_and_grows() {
+        let values: Vec<i64> = (0..150).collect();
+        let mut memory = Memory::new(&values);
+        assert_eq!(memory.len(), 150);
+        assert_eq!(memory.get(149), 149);
+        assert_eq!(memory.get(150), 0);
+        assert_eq!(memory.get(10_000), 0);
+
+        memory.set(1000, 7);
+        assert_eq!(memory.len(), 1001);
+        assert_eq!(memory.get(1000), 7);
+        assert_eq!(memory.get(999), 0);
+        assert_eq!(memory.to_vec()[..150], values[..]);
+    }
+
+    #[test]
+    fn copies_shared_pages_on_write() {
+        let values: Vec<i64> = (0..3 * PAGE as i64).collect();
+        let original = Memory::new(&values);
+        let mut copy = original.clone();
+        copy.set(PAGE + 1, -1);
+
+        assert_eq!(original.get(PAGE + 1), PAGE as i64 + 1);
+        assert_eq!(copy.get(PAGE + 1), -1);
+        assert_ne!(original, copy);
+        let shared: Vec<bool> = (0..3)
+            .map(|i| Arc::ptr_eq(&original.pages[i], &copy.pages[i]))
+            .collect();
+        assert_eq!(shared, [true, false, true]);
+
+        copy.set(PAGE + 1, PAGE as i64 + 1);
+        assert_eq!(original, copy);
+    }
+}
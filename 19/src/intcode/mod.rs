@@ -0,0 +1,794 @@
+pub mod ascii;
+pub mod asm;
+pub mod cfg;
+#[cfg(test)]
+mod conformance;
+pub mod debug;
+pub mod disasm;
+pub mod fast;
+pub mod jit;
+mod memory;
+pub mod network;
+pub mod symbolic;
+pub mod trace;
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+use memory::Memory;
+
+/// Programs can't write past this address, so that a stray write can't use
+/// up all the memory there is.
+pub const MEMORY_LIMIT: usize = 1 << 20;
+
+pub fn parse_memory(path: &str) -> anyhow::Result<Vec<i64>> {
+    memory_from_str(&std::fs::read_to_string(path)?)
+}
+
+pub fn memory_from_str(s: &str) -> anyhow::Result<Vec<i64>> {
+    let mut out = Vec::new();
+    for i in s.trim_end().split(',') {
+        out.push(i.parse()?);
+    }
+    Ok(out)
+}
+
+/// An Intcode machine. Cloning one is cheap, since the clones share memory
+/// until one of them writes to it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Computer {
+    memory: Memory,
+    pointer: usize,
+    relative_base: i64,
+    input: VecDeque<i64>,
+}
+
+/// A `Computer`'s state, to go back to later with `Computer::restore`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot(Computer);
+
+/// Why `Computer::run` stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    Halted,
+    /// The program wants input and the queue is empty.
+    NeedsInput,
+    Output(i64),
+}
+
+/// A program that can't carry on, and the instruction it got stuck at.
+#[derive(thiserror::Error, Clone, Copy, Debug, Eq, PartialEq)]
+#[error("{kind} at {pointer} (instruction {instruction})")]
+pub struct IntcodeError {
+    pub kind: ErrorKind,
+    pub pointer: usize,
+    pub instruction: i64,
+}
+
+#[derive(thiserror::Error, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// The pointer ran past the end of the program.
+    #[error("overran memory")]
+    OverranMemory,
+    #[error("unrecognised opcode")]
+    UnknownOpcode,
+    #[error("unrecognised parameter mode")]
+    UnknownMode,
+    #[error("can't write in immediate mode")]
+    ImmediateWrite,
+    /// An operand or jump pointed below address 0.
+    #[error("negative address {0}")]
+    NegativeAddress(i64),
+    /// A write to an address past `MEMORY_LIMIT`.
+    #[error("address {0} is past the memory limit")]
+    AddressTooLarge(i64),
+    #[error("arithmetic overflow")]
+    Overflow,
+    /// The program asked for input and `run_on` had none left to give.
+    #[error("no input provided")]
+    MissingInput,
+    /// `run_for` used up its steps without the program stopping.
+    #[error("ran out of steps")]
+    OutOfSteps,
+}
+
+impl Computer {
+    pub fn new(input: &[i64]) -> Computer {
+        Computer {
+            memory: Memory::new(input),
+            pointer: 0,
+            relative_base: 0,
+            input: VecDeque::new(),
+        }
+    }
+
+    /// Saves everything about the computer: memory, pointer, relative base
+    /// and queued input. Doesn't copy memory.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.clone())
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        *self = snapshot.0.clone();
+    }
+
+    /// A copy of memory, up to the highest address written.
+    pub fn memory(&self) -> Vec<i64> {
+        self.memory.to_vec()
+    }
+
+    /// The address of the next instruction to run.
+    pub fn pointer(&self) -> usize {
+        self.pointer
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    /// Queues `value` for the program's next input instruction.
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// Runs until the program halts, outputs a value or asks for input it
+    /// hasn't been given. Calling it again picks up where it stopped, so a
+    /// program waiting on input resumes once some has been pushed.
+    pub fn run(&mut self) -> Result<Status, IntcodeError> {
+        loop {
+            if let Some(status) = self.step()? {
+                return Ok(status);
+            }
+        }
+    }
+
+    /// Like `run`, but gives up after executing `steps` instructions without
+    /// stopping, for programs that might never stop. Calling it again after
+    /// that carries on.
+    pub fn run_for(&mut self, steps: usize) -> Result<Status, IntcodeError> {
+        for _ in 0..steps {
+            if let Some(status) = self.step()? {
+                return Ok(status);
+            }
+        }
+        Err(self.error(ErrorKind::OutOfSteps))
+    }
+
+    /// Runs to completion with at most one input, collecting the output.
+    pub fn run_on(&mut self, input: Option<i64>) -> Result<Vec<i64>, IntcodeError> {
+        self.input.extend(input);
+        let mut output = Vec::new();
+        loop {
+            match self.run()? {
+                Status::Halted => return Ok(output),
+                Status::NeedsInput => return Err(self.error(ErrorKind::MissingInput)),
+                Status::Output(n) => output.push(n),
+            }
+        }
+    }
+
+    /// Executes the instruction at the pointer, returning a status if it
+    /// stopped the program. The pointer doesn't move if it fails.
+    fn step(&mut self) -> Result<Option<Status>, IntcodeError> {
+        if self.pointer >= self.memory.len() {
+            return Err(self.error(ErrorKind::OverranMemory));
+        }
+        let code = self.memory.get(self.pointer);
+        let (op, modes) = parse_op(code).map_err(|kind| self.error(kind))?;
+        let mut params = [0; 3];
+        for (i, param) in params[..op.num_params()].iter_mut().enumerate() {
+            *param = self.memory.get(self.pointer + 1 + i);
+        }
+        let flow = execute(self, op, modes, params).map_err(|kind| self.error(kind))?;
+        Ok(flow.follow(&mut self.pointer, op))
+    }
+
+    /// An error at the instruction under the pointer.
+    fn error(&self, kind: ErrorKind) -> IntcodeError {
+        IntcodeError {
+            kind,
+            pointer: self.pointer,
+            instruction: self.memory.get(self.pointer),
+        }
+    }
+
+    /// Reads `index`, which is 0 if nothing has been written there yet.
+    /// Panics if `index` is negative.
+    pub fn get(&self, index: i64) -> i64 {
+        self.memory.get(address(index))
+    }
+
+    /// Writes `index`, growing memory to reach it if need be. Panics if
+    /// `index` is negative or past `MEMORY_LIMIT`.
+    pub fn set(&mut self, index: i64, value: i64) {
+        self.memory.set(write_address(index), value)
+    }
+}
+
+impl Cpu for Computer {
+    fn cell(&self, address: usize) -> i64 {
+        self.memory.get(address)
+    }
+
+    fn set_cell(&mut self, address: usize, value: i64) {
+        self.memory.set(address, value)
+    }
+
+    fn base(&self) -> i64 {
+        self.relative_base
+    }
+
+    fn set_base(&mut self, base: i64) {
+        self.relative_base = base;
+    }
+
+    fn input(&mut self) -> &mut VecDeque<i64> {
+        &mut self.input
+    }
+}
+
+/// What every Intcode backend can do, so that callers can be written once
+/// and switch between them. See `Computer` for what each method does.
+pub trait Machine {
+    fn push_input(&mut self, value: i64);
+    fn run(&mut self) -> Result<Status, IntcodeError>;
+    fn run_for(&mut self, steps: usize) -> Result<Status, IntcodeError>;
+    fn run_on(&mut self, input: Option<i64>) -> Result<Vec<i64>, IntcodeError>;
+    fn memory(&self) -> Vec<i64>;
+    fn pointer(&self) -> usize;
+    fn relative_base(&self) -> i64;
+    fn get(&self, index: i64) -> i64;
+    fn set(&mut self, index: i64, value: i64);
+}
+
+impl Machine for Computer {
+    fn push_input(&mut self, value: i64) {
+        Computer::push_input(self, value)
+    }
+
+    fn run(&mut self) -> Result<Status, IntcodeError> {
+        Computer::run(self)
+    }
+
+    fn run_for(&mut self, steps: usize) -> Result<Status, IntcodeError> {
+        Computer::run_for(self, steps)
+    }
+
+    fn run_on(&mut self, input: Option<i64>) -> Result<Vec<i64>, IntcodeError> {
+        Computer::run_on(self, input)
+    }
+
+    fn memory(&self) -> Vec<i64> {
+        Computer::memory(self)
+    }
+
+    fn pointer(&self) -> usize {
+        Computer::pointer(self)
+    }
+
+    fn relative_base(&self) -> i64 {
+        Computer::relative_base(self)
+    }
+
+    fn get(&self, index: i64) -> i64 {
+        Computer::get(self, index)
+    }
+
+    fn set(&mut self, index: i64, value: i64) {
+        Computer::set(self, index, value)
+    }
+}
+
+fn address(index: i64) -> usize {
+    usize::try_from(index).unwrap_or_else(|_| panic!("negative address {}", index))
+}
+
+/// `address`, but also panicking past `MEMORY_LIMIT`, so that a bad write
+/// can't use up all the memory there is.
+fn write_address(index: i64) -> usize {
+    let address = address(index);
+    if address >= MEMORY_LIMIT {
+        panic!("address {} is past the memory limit", index);
+    }
+    address
+}
+
+/// The longest an instruction can be: an op and three parameters.
+const MAX_LEN: usize = 4;
+
+/// The state an instruction runs against, so that `execute` can be shared
+/// between the backends.
+trait Cpu {
+    /// Reads `address`, which is 0 if nothing has been written there yet.
+    fn cell(&self, address: usize) -> i64;
+    /// Writes `address`, which is below `MEMORY_LIMIT`.
+    fn set_cell(&mut self, address: usize, value: i64);
+    fn base(&self) -> i64;
+    fn set_base(&mut self, base: i64);
+    fn input(&mut self) -> &mut VecDeque<i64>;
+}
+
+/// Where the pointer goes after an instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Flow {
+    Next,
+    /// To the next instruction, having output a value.
+    Output(i64),
+    Jump(usize),
+    /// Nowhere, because the program halted or is waiting for input.
+    Stop(Status),
+}
+
+impl Flow {
+    /// Moves `pointer` on from an `op` that went this way, returning the
+    /// status it stopped the program with, if any.
+    fn follow(self, pointer: &mut usize, op: Op) -> Option<Status> {
+        match self {
+            Flow::Next => {
+                *pointer += op.num_params() + 1;
+                None
+            }
+            Flow::Output(value) => {
+                *pointer += op.num_params() + 1;
+                Some(Status::Output(value))
+            }
+            Flow::Jump(target) => {
+                *pointer = target;
+                None
+            }
+            Flow::Stop(status) => Some(status),
+        }
+    }
+}
+
+/// Runs `op`, with `params` in `modes`, against `cpu`. Nothing changes if it
+/// fails.
+fn execute<C: Cpu>(
+    cpu: &mut C,
+    op: Op,
+    modes: [ParamMode; 3],
+    params: [i64; 3],
+) -> Result<Flow, ErrorKind> {
+    let read = |cpu: &C, i: usize| operand(modes[i], params[i], cpu.base(), |a| cpu.cell(a));
+    let write = |cpu: &mut C, i: usize, value: i64| {
+        cpu.set_cell(destination(modes[i], params[i], cpu.base())?, value);
+        Ok(Flow::Next)
+    };
+    match op {
+        Op::Halt => Ok(Flow::Stop(Status::Halted)),
+        Op::Add => {
+            let sum = read(cpu, 0)?.checked_add(read(cpu, 1)?);
+            write(cpu, 2, sum.ok_or(ErrorKind::Overflow)?)
+        }
+        Op::Multiply => {
+            let product = read(cpu, 0)?.checked_mul(read(cpu, 1)?);
+            write(cpu, 2, product.ok_or(ErrorKind::Overflow)?)
+        }
+        Op::Save => match cpu.input().front() {
+            Some(&input) => {
+                write(cpu, 0, input)?;
+                cpu.input().pop_front();
+                Ok(Flow::Next)
+            }
+            None => Ok(Flow::Stop(Status::NeedsInput)),
+        },
+        Op::Return => Ok(Flow::Output(read(cpu, 0)?)),
+        Op::JumpIfTrue | Op::JumpIfFalse => {
+            if (read(cpu, 0)? != 0) == (op == Op::JumpIfTrue) {
+                Ok(Flow::Jump(index(read(cpu, 1)?)?))
+            } else {
+                Ok(Flow::Next)
+            }
+        }
+        Op::LessThan => {
+            let less = read(cpu, 0)? < read(cpu, 1)?;
+            write(cpu, 2, less.into())
+        }
+        Op::Equals => {
+            let equal = read(cpu, 0)? == read(cpu, 1)?;
+            write(cpu, 2, equal.into())
+        }
+        Op::AdjustBase => {
+            let base = cpu.base().checked_add(read(cpu, 0)?);
+            cpu.set_base(base.ok_or(ErrorKind::Overflow)?);
+            Ok(Flow::Next)
+        }
+    }
+}
+
+/// The value of a parameter read in `mode`, fetching addresses with `cell`.
+fn operand(
+    mode: ParamMode,
+    param: i64,
+    relative_base: i64,
+    cell: impl FnOnce(usize) -> i64,
+) -> Result<i64, ErrorKind> {
+    match mode {
+        ParamMode::Position => Ok(cell(index(param)?)),
+        ParamMode::Immediate => Ok(param),
+        ParamMode::Relative => Ok(cell(index(relative(relative_base, param)?)?)),
+    }
+}
+
+/// The address a parameter written in `mode` refers to.
+fn destination(mode: ParamMode, param: i64, relative_base: i64) -> Result<usize, ErrorKind> {
+    let address = match mode {
+        ParamMode::Position => param,
+        ParamMode::Immediate => return Err(ErrorKind::ImmediateWrite),
+        ParamMode::Relative => relative(relative_base, param)?,
+    };
+    let index = index(address)?;
+    if index >= MEMORY_LIMIT {
+        return Err(ErrorKind::AddressTooLarge(address));
+    }
+    Ok(index)
+}
+
+/// `offset` from the relative base.
+fn relative(relative_base: i64, offset: i64) -> Result<i64, ErrorKind> {
+    relative_base.checked_add(offset).ok_or(ErrorKind::Overflow)
+}
+
+/// `address` as an index into memory, failing if it's negative.
+fn index(address: i64) -> Result<usize, ErrorKind> {
+    usize::try_from(address).map_err(|_| ErrorKind::NegativeAddress(address))
+}
+
+/// An instruction, without its parameters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Op {
+    Add,
+    Multiply,
+    Save,
+    Return,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustBase,
+    Halt,
+}
+
+impl Op {
+    pub const ALL: [Op; 10] = [
+        Op::Add,
+        Op::Multiply,
+        Op::Save,
+        Op::Return,
+        Op::JumpIfTrue,
+        Op::JumpIfFalse,
+        Op::LessThan,
+        Op::Equals,
+        Op::AdjustBase,
+        Op::Halt,
+    ];
+
+    pub fn code(&self) -> usize {
+        match self {
+            Op::Add => 1,
+            Op::Multiply => 2,
+            Op::Save => 3,
+            Op::Return => 4,
+            Op::JumpIfTrue => 5,
+            Op::JumpIfFalse => 6,
+            Op::LessThan => 7,
+            Op::Equals => 8,
+            Op::AdjustBase => 9,
+            Op::Halt => 99,
+        }
+    }
+
+    pub fn from_code(n: usize) -> Option<Op> {
+        Op::ALL.iter().copied().find(|op| op.code() == n)
+    }
+
+    pub fn num_params(&self) -> usize {
+        match self {
+            Op::Add => 3,
+            Op::Multiply => 3,
+            Op::Save => 1,
+            Op::Return => 1,
+            Op::JumpIfTrue => 2,
+            Op::JumpIfFalse => 2,
+            Op::LessThan => 3,
+            Op::Equals => 3,
+            Op::AdjustBase => 1,
+            Op::Halt => 0,
+        }
+    }
+
+    /// Which parameter, if any, the instruction writes its result to.
+    pub fn output_param(&self) -> Option<usize> {
+        match self {
+            Op::Add | Op::Multiply | Op::LessThan | Op::Equals => Some(2),
+            Op::Save => Some(0),
+            _ => None,
+        }
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Multiply => "mul",
+            Op::Save => "in",
+            Op::Return => "out",
+            Op::JumpIfTrue => "jt",
+            Op::JumpIfFalse => "jf",
+            Op::LessThan => "lt",
+            Op::Equals => "eq",
+            Op::AdjustBase => "arb",
+            Op::Halt => "halt",
+        }
+    }
+}
+
+/// How an instruction interprets one of its parameters.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParamMode {
+    #[default]
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl ParamMode {
+    pub fn from_code(n: usize) -> Option<ParamMode> {
+        match n {
+            0 => Some(ParamMode::Position),
+            1 => Some(ParamMode::Immediate),
+            2 => Some(ParamMode::Relative),
+            _ => None,
+        }
+    }
+}
+
+/// Splits an instruction's first cell into its op and the modes of its
+/// parameters. Modes past the op's parameters are left as `Position`.
+fn parse_op(code: i64) -> Result<(Op, [ParamMode; 3]), ErrorKind> {
+    let code = usize::try_from(code).map_err(|_| ErrorKind::UnknownOpcode)?;
+    let op = Op::from_code(code % 100).ok_or(ErrorKind::UnknownOpcode)?;
+
+    let mut modes = [ParamMode::Position; 3];
+    let mut mode_code = code / 100;
+    for mode in &mut modes[..op.num_params()] {
+        *mode = ParamMode::from_code(mode_code % 10).ok_or(ErrorKind::UnknownMode)?;
+        mode_code /= 10;
+    }
+
+    Ok((op, modes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn basics() {
+        let tests: Vec<(&[i64], &[i64])> = vec![
+            (&[1, 0, 0, 0, 99], &[2, 0, 0, 0, 99]),
+            (&[2, 3, 0, 3, 99], &[2, 3, 0, 6, 99]),
+            (&[2, 4, 4, 5, 99, 0], &[2, 4, 4, 5, 99, 9801]),
+            (
+                &[1, 1, 1, 4, 99, 5, 6, 0, 99],
+                &[30, 1, 1, 4, 2, 5, 6, 0, 99],
+            ),
+            (
+                &[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50],
+                &[3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50],
+            ),
+        ];
+        for (input, expected) in tests {
+            let mut mem = Computer::new(input);
+            mem.run().unwrap();
+            assert_eq!(mem.memory(), expected);
+        }
+    }
+
+    #[test]
+    fn parameter_modes() {
+        let mut mem = Computer::new(&[1002, 4, 3, 4, 33]);
+        mem.run().unwrap();
+        assert_eq!(mem.memory(), [1002, 4, 3, 4, 99]);
+    }
+
+    #[test]
+    fn save_return() {
+        let mut mem = Computer::new(&[3, 0, 4, 0, 99]);
+        assert_eq!(mem.run_on(Some(1066)).unwrap(), vec![1066]);
+    }
+
+    #[test]
+    fn resumes_on_input() {
+        // Doubles every input until it's given a zero.
+        let mut computer =
+            Computer::new(&[3, 15, 1006, 15, 14, 102, 2, 15, 16, 4, 16, 1105, 1, 0, 99]);
+        assert_eq!(computer.run(), Ok(Status::NeedsInput));
+        assert_eq!(computer.run(), Ok(Status::NeedsInput));
+        computer.push_input(4);
+        computer.push_input(5);
+        assert_eq!(computer.run(), Ok(Status::Output(8)));
+        assert_eq!(computer.run(), Ok(Status::Output(10)));
+        assert_eq!(computer.run(), Ok(Status::NeedsInput));
+        computer.push_input(0);
+        assert_eq!(computer.run(), Ok(Status::Halted));
+        assert_eq!(computer.run(), Ok(Status::Halted));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |program: &[i64]| {
+            let mut computer = Computer::new(program);
+            let error = computer.run_on(Some(1)).unwrap_err();
+            // Failing leaves the pointer at the bad instruction.
+            assert_eq!(computer.pointer, error.pointer);
+            (error.kind, error.pointer, error.instruction)
+        };
+        use ErrorKind::*;
+        assert_eq!(error(&[3, 0, 3, 0, 99]), (MissingInput, 2, 3));
+        assert_eq!(
+            error(&[1101, 1, 1, 5, 1105, 1, 100]),
+            (OverranMemory, 100, 0)
+        );
+        assert_eq!(error(&[1101, 1, 1, 5, 98]), (UnknownOpcode, 4, 98));
+        assert_eq!(error(&[-1]), (UnknownOpcode, 0, -1));
+        assert_eq!(error(&[104, 0, 304, 0, 99]), (UnknownMode, 2, 304));
+        assert_eq!(error(&[11101, 1, 1, 0, 99]), (ImmediateWrite, 0, 11101));
+        assert_eq!(error(&[4, -3, 99]), (NegativeAddress(-3), 0, 4));
+        assert_eq!(error(&[109, -5, 203, 0, 99]), (NegativeAddress(-5), 2, 203));
+        assert_eq!(error(&[1105, 1, -7]), (NegativeAddress(-7), 0, 1105));
+        assert_eq!(error(&[1101, i64::MAX, 1, 0, 99]), (Overflow, 0, 1101));
+        assert_eq!(error(&[109, i64::MIN, 109, -1, 99]), (Overflow, 2, 109));
+        let too_far = MEMORY_LIMIT as i64;
+        assert_eq!(
+            error(&[1101, 1, 1, too_far, 99]),
+            (AddressTooLarge(too_far), 0, 1101)
+        );
+
+        let mut computer = Computer::new(&[1105, 1, 0]);
+        let error = computer.run_for(10).unwrap_err();
+        assert_eq!((error.kind, error.pointer), (OutOfSteps, 0));
+        assert_eq!(Computer::new(&[99]).run_for(1), Ok(Status::Halted));
+
+        let error = Computer::new(&[3, 0, 3, 0, 99]).run_on(None).unwrap_err();
+        assert_eq!(error.to_string(), "no input provided at 0 (instruction 3)");
+    }
+
+    #[test]
+    fn snapshots() {
+        // Adds two inputs.
+        let mut computer = Computer::new(&[3, 100, 3, 101, 1, 100, 101, 102, 4, 102, 99]);
+        computer.push_input(2);
+        assert_eq!(computer.run(), Ok(Status::NeedsInput));
+        let saved = computer.snapshot();
+
+        computer.push_input(3);
+        assert_eq!(computer.run(), Ok(Status::Output(5)));
+        computer.restore(&saved);
+        assert_eq!(computer.get(102), 0);
+        computer.push_input(40);
+        assert_eq!(computer.run(), Ok(Status::Output(42)));
+
+        let mut fork = computer.clone();
+        fork.set(102, 0);
+        assert_eq!(computer.get(102), 42);
+
+        let mut computer = Computer::new(&[3, 0, 4, 0, 99]);
+        computer.push_input(7);
+        let saved = computer.snapshot();
+        assert_eq!(computer.run_on(None).unwrap(), vec![7]);
+        computer.restore(&saved);
+        assert_eq!(computer, saved.0);
+        assert_eq!(computer.run_on(None).unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn comparisons() {
+        type Cmp = Box<dyn Fn(i64, i64) -> bool>;
+        let tests: Vec<(&[i64], Cmp)> = vec![
+            (
+                &[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8],
+                Box::new(|x, y| x == y),
+            ),
+            (
+                &[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8],
+                Box::new(|x, y| x < y),
+            ),
+            (&[3, 3, 1108, -1, 8, 3, 4, 3, 99], Box::new(|x, y| x == y)),
+            (&[3, 3, 1107, -1, 8, 3, 4, 3, 99], Box::new(|x, y| x < y)),
+        ];
+
+        for (input, cmp) in tests {
+            for n in 0..16 {
+                let mut computer = Computer::new(input);
+                let output = computer.run_on(Some(n)).unwrap();
+                let expected = vec![i64::from(cmp(n, 8))];
+                assert_eq!(
+                    output, expected,
+                    "memory = {:?}, input = {}, got {:?}, want {:?}",
+                    input, n, output, expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn jumps() {
+        let tests: Vec<&[i64]> = vec![
+            &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+            &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1],
+        ];
+
+        for input in tests {
+            for n in 0..5 {
+                let mut computer = Computer::new(input);
+                let output = computer.run_on(Some(n)).unwrap();
+                let expected = vec![i64::from(n != 0)];
+                assert_eq!(
+                    output, expected,
+                    "memory = {:?}, input = {}, got {:?}, want {:?}",
+                    input, n, output, expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn jumps_galore() {
+        let input = &[
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+
+        for n in 6..11 {
+            let mut computer = Computer::new(input);
+            let output = computer.run_on(Some(n)).unwrap();
+            let expected = vec![match n.cmp(&8) {
+                Ordering::Less => 999,
+                Ordering::Equal => 1000,
+                Ordering::Greater => 1001,
+            }];
+            assert_eq!(
+                output, expected,
+                "input = {}, got {:?}, want {:?}",
+                n, output, expected
+            );
+        }
+    }
+
+    #[test]
+    fn relative_base() {
+        let quine = &[
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        assert_eq!(Computer::new(quine).run_on(None).unwrap(), quine);
+
+        let mut computer = Computer::new(&[109, 19, 204, -34, 99]);
+        computer.relative_base = 2000;
+        computer.set(1985, 7);
+        assert_eq!(computer.run_on(None).unwrap(), vec![7]);
+        assert_eq!(computer.relative_base, 2019);
+    }
+
+    #[test]
+    fn large_numbers() {
+        let mut computer = Computer::new(&[1102, 34915192, 34915192, 7, 4, 7, 99, 0]);
+        assert_eq!(computer.run_on(None).unwrap(), vec![1219070632396864]);
+
+        let mut computer = Computer::new(&[104, 1125899906842624, 99]);
+        assert_eq!(computer.run_on(None).unwrap(), vec![1125899906842624]);
+    }
+
+    #[test]
+    fn memory_grows() {
+        let mut computer = Computer::new(&[1101, 2, 3, 1000, 4, 1000, 4, 500, 99]);
+        assert_eq!(computer.run_on(None).unwrap(), vec![5, 0]);
+        assert_eq!(computer.memory().len(), 1001);
+        assert_eq!(computer.get(5000), 0);
+
+        let mut computer = Computer::new(&[109, 10, 21101, 6, 7, 5, 204, 5, 99]);
+        assert_eq!(computer.run_on(None).unwrap(), vec![13]);
+        assert_eq!(computer.get(15), 13);
+    }
+}
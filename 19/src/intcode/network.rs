@@ -0,0 +1,307 @@
+//! Runs several `Computer`s together, passing values between their input and
+//! output queues. Everything happens on one thread in a fixed order, so a
+//! given set of programs always produces the same result.
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+use super::{Computer, ErrorKind, IntcodeError, Status};
+
+/// Computers wired so that each one's output is the next one's input.
+pub struct Pipeline {
+    computers: Vec<Computer>,
+    feedback: bool,
+}
+
+impl Pipeline {
+    /// A line of computers: the last one's output leaves the pipeline.
+    pub fn chain(computers: Vec<Computer>) -> Pipeline {
+        Pipeline {
+            computers,
+            feedback: false,
+        }
+    }
+
+    /// A ring of computers: the last one's output leaves the pipeline and is
+    /// also fed back to the first.
+    pub fn feedback_loop(computers: Vec<Computer>) -> Pipeline {
+        Pipeline {
+            computers,
+            feedback: true,
+        }
+    }
+
+    /// Gives `input` to the first computer and runs them all in turn until
+    /// every one has halted, returning everything the last one output. If
+    /// they're all stuck waiting for input, fails with the first one that is.
+    /// An empty pipeline halts straight away, with no output.
+    pub fn run(&mut self, input: impl IntoIterator<Item = i64>) -> Result<Vec<i64>, IntcodeError> {
+        let last = match self.computers.len().checked_sub(1) {
+            Some(last) => last,
+            None => return Ok(Vec::new()),
+        };
+        for value in input {
+            self.computers[0].push_input(value);
+        }
+        let mut output = Vec::new();
+        let mut halted = vec![false; self.computers.len()];
+        loop {
+            let mut moved = false;
+            for (i, halted) in halted.iter_mut().enumerate() {
+                loop {
+                    match self.computers[i].run()? {
+                        Status::Halted => {
+                            *halted = true;
+                            break;
+                        }
+                        Status::NeedsInput => break,
+                        Status::Output(value) => {
+                            moved = true;
+                            if i < last {
+                                self.computers[i + 1].push_input(value);
+                            } else {
+                                output.push(value);
+                                if self.feedback {
+                                    self.computers[0].push_input(value);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if halted.iter().all(|&h| h) {
+                return Ok(output);
+            }
+            if !moved {
+                let stuck = halted.iter().position(|&h| !h).unwrap();
+                return Err(self.computers[stuck].error(ErrorKind::MissingInput));
+            }
+        }
+    }
+}
+
+/// A value sent from one computer in a `Network` to another.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Packet {
+    pub to: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+/// Why `Network::run` stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// A packet was sent to an address outside the network.
+    Packet(Packet),
+    /// Every computer is waiting for packets and none are in flight.
+    Idle,
+    /// Every computer has halted.
+    Halted,
+}
+
+/// Computers that send each other packets as three outputs: an address, then
+/// `x` and `y`. Each computer is told its address as its first input, and is
+/// given -1 whenever it asks for input while it has no packets waiting.
+pub struct Network {
+    computers: Vec<Computer>,
+    halted: Vec<bool>,
+    sending: Vec<Vec<i64>>,
+    outbox: VecDeque<Packet>,
+}
+
+impl Network {
+    /// `size` computers running `program`, at addresses `0..size`.
+    pub fn new(program: &[i64], size: usize) -> Network {
+        let computers = (0..size)
+            .map(|address| {
+                let mut computer = Computer::new(program);
+                computer.push_input(address as i64);
+                computer
+            })
+            .collect();
+        Network {
+            computers,
+            halted: vec![false; size],
+            sending: vec![Vec::new(); size],
+            outbox: VecDeque::new(),
+        }
+    }
+
+    /// Queues `packet` for the computer it's addressed to, returning it back
+    /// if there's no such computer.
+    pub fn send(&mut self, packet: Packet) -> Result<(), Packet> {
+        let computer = usize::try_from(packet.to)
+            .ok()
+            .and_then(|to| self.computers.get_mut(to))
+            .ok_or(packet)?;
+        computer.push_input(packet.x);
+        computer.push_input(packet.y);
+        Ok(())
+    }
+
+    /// Gives each computer a turn, in address order, until a packet leaves
+    /// the network, the network goes idle, or everything has halted. Packets
+    /// between computers are delivered as soon as their sender's turn ends.
+    pub fn run(&mut self) -> Result<Event, IntcodeError> {
+        loop {
+            if let Some(packet) = self.outbox.pop_front() {
+                return Ok(Event::Packet(packet));
+            }
+            if self.halted.iter().all(|&h| h) {
+                return Ok(Event::Halted);
+            }
+            let mut idle = true;
+            for i in 0..self.computers.len() {
+                if self.halted[i] {
+                    continue;
+                }
+                if self.computers[i].input.is_empty() {
+                    self.computers[i].push_input(-1);
+                } else {
+                    idle = false;
+                }
+                let mut packets = Vec::new();
+                loop {
+                    match self.computers[i].run()? {
+                        Status::Halted => {
+                            self.halted[i] = true;
+                            break;
+                        }
+                        Status::NeedsInput => break,
+                        Status::Output(value) => {
+                            let sending = &mut self.sending[i];
+                            sending.push(value);
+                            if let [to, x, y] = sending[..] {
+                                packets.push(Packet { to, x, y });
+                                sending.clear();
+                            }
+                        }
+                    }
+                }
+                for packet in packets {
+                    idle = false;
+                    if let Err(packet) = self.send(packet) {
+                        self.outbox.push_back(packet);
+                    }
+                }
+            }
+            if idle {
+                return Ok(Event::Idle);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amplifiers(program: &[i64], phases: &[i64]) -> Vec<Computer> {
+        phases
+            .iter()
+            .map(|&phase| {
+                let mut computer = Computer::new(program);
+                computer.push_input(phase);
+                computer
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chain() {
+        let program = &[
+            3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+        ];
+        let mut pipeline = Pipeline::chain(amplifiers(program, &[4, 3, 2, 1, 0]));
+        assert_eq!(pipeline.run(Some(0)).unwrap(), vec![43210]);
+
+        let program = &[
+            3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33, 1,
+            33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
+        ];
+        let mut pipeline = Pipeline::chain(amplifiers(program, &[1, 0, 4, 3, 2]));
+        assert_eq!(pipeline.run(Some(0)).unwrap(), vec![65210]);
+    }
+
+    #[test]
+    fn feedback_loop() {
+        let program = &[
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let mut pipeline = Pipeline::feedback_loop(amplifiers(program, &[9, 8, 7, 6, 5]));
+        assert_eq!(pipeline.run(Some(0)).unwrap().last(), Some(&139629729));
+    }
+
+    #[test]
+    fn empty_pipelines_halt() {
+        assert_eq!(Pipeline::chain(Vec::new()).run(Some(0)), Ok(Vec::new()));
+        assert_eq!(
+            Pipeline::feedback_loop(Vec::new()).run(None),
+            Ok(Vec::new())
+        );
+    }
+
+    #[test]
+    fn stalled_pipeline() {
+        let error = Pipeline::chain(amplifiers(&[3, 0, 3, 0, 99], &[1]))
+            .run(None)
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::MissingInput);
+        assert_eq!((error.pointer, error.instruction), (2, 3));
+    }
+
+    /// Passes each packet `(x, y)` on to the next address as `(x, y + address)`.
+    const RELAY: &[i64] = &[
+        3, 100, 3, 101, 1008, 101, -1, 103, 1005, 103, 2, 3, 102, 1001, 100, 1, 104, 4, 104, 4,
+        101, 1, 102, 100, 102, 4, 102, 1105, 1, 2,
+    ];
+
+    #[test]
+    fn network() {
+        let mut network = Network::new(RELAY, 5);
+        assert_eq!(network.run().unwrap(), Event::Idle);
+        network
+            .send(Packet {
+                to: 0,
+                x: 7,
+                y: 100,
+            })
+            .unwrap();
+        assert_eq!(
+            network.run().unwrap(),
+            Event::Packet(Packet {
+                to: 5,
+                x: 7,
+                y: 110
+            })
+        );
+        assert_eq!(network.run().unwrap(), Event::Idle);
+        assert!(network.send(Packet { to: 5, x: 0, y: 0 }).is_err());
+
+        // A NAT that restarts the ring with whatever last left it.
+        let mut nat = Packet {
+            to: 0,
+            x: 7,
+            y: 110,
+        };
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            match network.run().unwrap() {
+                Event::Packet(packet) => {
+                    seen.push(packet.y);
+                    nat = packet;
+                }
+                Event::Idle => network.send(Packet { to: 0, ..nat }).unwrap(),
+                Event::Halted => unreachable!(),
+            }
+        }
+        assert_eq!(seen, [120, 130]);
+    }
+
+    #[test]
+    fn halted_network() {
+        let mut network = Network::new(&[3, 0, 99], 3);
+        assert_eq!(network.run().unwrap(), Event::Halted);
+    }
+}
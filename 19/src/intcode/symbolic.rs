@@ -0,0 +1,596 @@
+//! Solving for a program's inputs instead of searching for them.
+//!
+//! Some cells or inputs are marked as symbols, and the program is run once
+//! with each symbol at the bottom of its range while every value it computes
+//! is shadowed by a linear expression over the symbols. If control flow
+//! never depends on a symbol, the goal's expression holds for every
+//! assignment, and solving it gives the answer directly. Anything that stops
+//! the expressions being linear, or lets a symbol steer the program, falls
+//! back to trying every assignment in turn.
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use super::fast::FastComputer;
+use super::trace::{Step, Stop, Trace};
+use super::{Computer, ErrorKind, IntcodeError, Op, ParamMode, Status, MEMORY_LIMIT};
+
+/// `constant + Σ coefficients[i] × symbol i`. Coefficients missing from the
+/// end are 0.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Linear {
+    pub constant: i64,
+    pub coefficients: Vec<i64>,
+}
+
+impl Linear {
+    pub fn constant(n: i64) -> Linear {
+        Linear {
+            constant: n,
+            coefficients: Vec::new(),
+        }
+    }
+
+    pub fn symbol(i: usize) -> Linear {
+        let mut coefficients = vec![0; i + 1];
+        coefficients[i] = 1;
+        Linear {
+            constant: 0,
+            coefficients,
+        }
+    }
+
+    /// The expression's value if it doesn't depend on any symbol.
+    pub fn as_constant(&self) -> Option<i64> {
+        if self.coefficients.iter().all(|&c| c == 0) {
+            Some(self.constant)
+        } else {
+            None
+        }
+    }
+
+    pub fn coefficient(&self, i: usize) -> i64 {
+        self.coefficients.get(i).copied().unwrap_or(0)
+    }
+
+    /// The expression's value with symbol i as `values[i]`, or `None` if it
+    /// overflows.
+    pub fn eval(&self, values: &[i64]) -> Option<i64> {
+        self.coefficients
+            .iter()
+            .zip(values)
+            .try_fold(self.constant, |sum, (&c, &v)| {
+                sum.checked_add(c.checked_mul(v)?)
+            })
+    }
+
+    fn add(&self, other: &Linear) -> Option<Linear> {
+        let len = self.coefficients.len().max(other.coefficients.len());
+        Some(Linear {
+            constant: self.constant.checked_add(other.constant)?,
+            coefficients: (0..len)
+                .map(|i| self.coefficient(i).checked_add(other.coefficient(i)))
+                .collect::<Option<_>>()?,
+        })
+    }
+
+    fn scale(&self, k: i64) -> Option<Linear> {
+        Some(Linear {
+            constant: self.constant.checked_mul(k)?,
+            coefficients: self
+                .coefficients
+                .iter()
+                .map(|c| c.checked_mul(k))
+                .collect::<Option<_>>()?,
+        })
+    }
+
+    /// `None` unless one side is a constant.
+    fn mul(&self, other: &Linear) -> Option<Linear> {
+        match (self.as_constant(), other.as_constant()) {
+            (_, Some(k)) => self.scale(k),
+            (Some(k), _) => other.scale(k),
+            (None, None) => None,
+        }
+    }
+
+    /// `None` unless the comparison comes out the same for every assignment,
+    /// which it does when the two sides differ by a constant.
+    fn compare(&self, other: &Linear, op: Op) -> Option<Linear> {
+        let difference = self.add(&other.scale(-1)?)?.as_constant()?;
+        let result = match op {
+            Op::LessThan => difference < 0,
+            _ => difference == 0,
+        };
+        Some(Linear::constant(result.into()))
+    }
+}
+
+impl fmt::Display for Linear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut terms: Vec<String> = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c != 0)
+            .map(|(i, &c)| match c {
+                1 => format!("s{}", i),
+                _ => format!("{}*s{}", c, i),
+            })
+            .collect();
+        if self.constant != 0 || terms.is_empty() {
+            terms.push(self.constant.to_string());
+        }
+        write!(f, "{}", terms.join(" + "))
+    }
+}
+
+/// Where a symbol goes into the program.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Slot {
+    /// The value of a cell of memory before the program starts.
+    Cell(usize),
+    /// The next input, after any already queued and any earlier `Input`
+    /// symbols.
+    Input,
+}
+
+/// The value a solution has to fix.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Goal {
+    /// A cell of memory once the program halts.
+    Cell(usize),
+    /// The nth value the program outputs.
+    Output(usize),
+}
+
+/// Why the goal couldn't be solved for directly.
+#[derive(thiserror::Error, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fallback {
+    #[error("the instruction at {0} depends on the symbols")]
+    SymbolicCode(usize),
+    #[error("the jump at {0} depends on the symbols")]
+    SymbolicBranch(usize),
+    #[error("an address used at {0} depends on the symbols")]
+    SymbolicAddress(usize),
+    #[error("the goal isn't linear in the symbols")]
+    NotLinear,
+    #[error("the program never produced the goal")]
+    NoGoal,
+    #[error("with each symbol at its lowest, {0}")]
+    Crashed(IntcodeError),
+}
+
+/// Values that reach the goal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Solution {
+    /// One per symbol, in the order they were added.
+    pub values: Vec<i64>,
+    /// Why every assignment had to be tried, if one did.
+    pub fallback: Option<Fallback>,
+}
+
+/// A program with some of its inputs left open.
+pub struct Solver {
+    computer: Computer,
+    symbols: Vec<(Slot, RangeInclusive<i64>)>,
+}
+
+impl Solver {
+    pub fn new(program: &[i64]) -> Solver {
+        Solver::from(Computer::new(program))
+    }
+
+    /// Adds a symbol at `slot` that can take any value in `range`. Fails if
+    /// `slot` is a cell past `MEMORY_LIMIT`.
+    pub fn symbol(mut self, slot: Slot, range: RangeInclusive<i64>) -> Result<Solver, ErrorKind> {
+        if let Slot::Cell(address) = slot {
+            if address >= MEMORY_LIMIT {
+                return Err(ErrorKind::AddressTooLarge(address as i64));
+            }
+        }
+        self.symbols.push((slot, range));
+        Ok(self)
+    }
+
+    /// `goal` as an expression over the symbols.
+    pub fn symbolic(&self, goal: Goal) -> Result<Linear, Fallback> {
+        let mut computer = self.computer.clone();
+        let mut input: VecDeque<Linear> = computer
+            .input
+            .iter()
+            .map(|&n| Linear::constant(n))
+            .collect();
+        let mut shadow = HashMap::new();
+        for (i, (slot, range)) in self.symbols.iter().enumerate() {
+            match *slot {
+                Slot::Cell(address) => {
+                    computer.set(address as i64, *range.start());
+                    shadow.insert(address, Some(Linear::symbol(i)));
+                }
+                Slot::Input => {
+                    computer.push_input(*range.start());
+                    input.push_back(Linear::symbol(i));
+                }
+            }
+        }
+        let mut concolic = Concolic {
+            computer,
+            shadow,
+            input,
+            output: Vec::new(),
+        };
+        concolic.run()?;
+        let expr = match goal {
+            Goal::Cell(address) => concolic.read(address),
+            Goal::Output(i) => concolic.output.get(i).cloned().ok_or(Fallback::NoGoal)?,
+        };
+        expr.ok_or(Fallback::NotLinear)
+    }
+
+    /// The first assignment, counting up through each symbol's range with
+    /// the last symbol changing fastest, that makes `goal` equal `target`.
+    /// Solved for directly if possible, and by trying every assignment if
+    /// not.
+    pub fn solve(&self, goal: Goal, target: i64) -> Option<Solution> {
+        let template = FastComputer::from(self.computer.clone());
+        let reaches = |values: &Vec<i64>| self.reaches(&template, values, goal) == Some(target);
+        let ranges: Vec<_> = self.symbols.iter().map(|(_, r)| r.clone()).collect();
+        match self.symbolic(goal) {
+            Ok(expr) => solutions(&expr, target, &ranges)
+                .find(reaches)
+                .map(|values| Solution {
+                    values,
+                    fallback: None,
+                }),
+            Err(fallback) => assignments(ranges).find(reaches).map(|values| Solution {
+                values,
+                fallback: Some(fallback),
+            }),
+        }
+    }
+
+    /// What `goal` comes out as with the symbols set to `values`, or `None`
+    /// if the program fails first.
+    fn reaches(&self, template: &FastComputer, values: &[i64], goal: Goal) -> Option<i64> {
+        let mut computer = template.clone();
+        for ((slot, _), &value) in self.symbols.iter().zip(values) {
+            match *slot {
+                Slot::Cell(address) => computer.set(address as i64, value),
+                Slot::Input => computer.push_input(value),
+            }
+        }
+        let output = computer.run_on(None).ok()?;
+        match goal {
+            Goal::Cell(address) => Some(computer.get(address as i64)),
+            Goal::Output(i) => output.get(i).copied(),
+        }
+    }
+}
+
+impl From<Computer> for Solver {
+    fn from(computer: Computer) -> Solver {
+        Solver {
+            computer,
+            symbols: Vec::new(),
+        }
+    }
+}
+
+/// A computer running with concrete values, and expressions for whichever
+/// of its cells depend on the symbols. An expression of `None` is a value
+/// read through an address that depends on the symbols, which can't be
+/// written down.
+struct Concolic {
+    computer: Computer,
+    shadow: HashMap<usize, Option<Linear>>,
+    input: VecDeque<Linear>,
+    output: Vec<Option<Linear>>,
+}
+
+impl Concolic {
+    fn run(&mut self) -> Result<(), Fallback> {
+        let mut trace = Trace::new(1);
+        loop {
+            let base = self.computer.relative_base;
+            let stop = self
+                .computer
+                .step_traced(&mut trace)
+                .map_err(Fallback::Crashed)?;
+            if stop == Some(Stop::Status(Status::NeedsInput)) {
+                let error = self.computer.error(ErrorKind::MissingInput);
+                return Err(Fallback::Crashed(error));
+            }
+            let step = trace.history().last().expect("the step was recorded");
+            self.shadow_step(step, base)?;
+            if stop == Some(Stop::Status(Status::Halted)) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// The expression for the cell at `address`.
+    fn read(&self, address: usize) -> Option<Linear> {
+        match self.shadow.get(&address) {
+            Some(expr) => expr.clone(),
+            None => Some(Linear::constant(self.computer.get(address as i64))),
+        }
+    }
+
+    /// Follows `step`, which ran with the relative base at `base`, through
+    /// the shadow.
+    fn shadow_step(&mut self, step: &Step, base: i64) -> Result<(), Fallback> {
+        let pointer = step.pointer;
+        if self.shadow.contains_key(&pointer) {
+            return Err(Fallback::SymbolicCode(pointer));
+        }
+        let operand = |i: usize| -> Option<Linear> {
+            let Step {
+                operands, values, ..
+            } = step;
+            match (self.shadow.get(&(pointer + 1 + i)), operands[i].mode) {
+                (Some(expr), ParamMode::Immediate) => expr.clone(),
+                (Some(_), _) => None,
+                (None, ParamMode::Immediate) => Some(Linear::constant(values[i])),
+                (None, ParamMode::Position) => self.read(operands[i].value as usize),
+                (None, ParamMode::Relative) => self.read((base + operands[i].value) as usize),
+            }
+        };
+        let constant = |i: usize| operand(i).and_then(|expr| expr.as_constant());
+        let result = match step.op {
+            Op::Add => operand(0).zip(operand(1)).and_then(|(x, y)| x.add(&y)),
+            Op::Multiply => operand(0).zip(operand(1)).and_then(|(x, y)| x.mul(&y)),
+            Op::LessThan | Op::Equals => operand(0)
+                .zip(operand(1))
+                .and_then(|(x, y)| x.compare(&y, step.op)),
+            Op::Save => self.input.pop_front(),
+            Op::Return => {
+                let output = operand(0);
+                self.output.push(output);
+                return Ok(());
+            }
+            Op::JumpIfTrue | Op::JumpIfFalse => {
+                let taken = match constant(0) {
+                    Some(condition) => (condition != 0) == (step.op == Op::JumpIfTrue),
+                    None => return Err(Fallback::SymbolicBranch(pointer)),
+                };
+                if taken && constant(1).is_none() {
+                    return Err(Fallback::SymbolicBranch(pointer));
+                }
+                return Ok(());
+            }
+            Op::AdjustBase => {
+                return match constant(0) {
+                    Some(_) => Ok(()),
+                    None => Err(Fallback::SymbolicAddress(pointer)),
+                };
+            }
+            Op::Halt => return Ok(()),
+        };
+        let output_param = step.op.output_param().expect("only writes are left");
+        if self.shadow.contains_key(&(pointer + 1 + output_param)) {
+            return Err(Fallback::SymbolicAddress(pointer));
+        }
+        let (address, _) = step.write.expect("writes record their address");
+        let address = usize::try_from(address).expect("the write succeeded");
+        match result {
+            Some(expr) if expr.as_constant().is_some() => {
+                self.shadow.remove(&address);
+            }
+            expr => {
+                self.shadow.insert(address, expr);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every assignment of values from `ranges`, counting up with the last
+/// changing fastest.
+fn assignments(ranges: Vec<RangeInclusive<i64>>) -> impl Iterator<Item = Vec<i64>> {
+    let mut next = if ranges.iter().any(|r| r.is_empty()) {
+        None
+    } else {
+        Some(ranges.iter().map(|r| *r.start()).collect::<Vec<_>>())
+    };
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        let mut following = current.clone();
+        for (value, range) in following.iter_mut().zip(&ranges).rev() {
+            if *value < *range.end() {
+                *value += 1;
+                next = Some(following);
+                break;
+            }
+            *value = *range.start();
+        }
+        Some(current)
+    })
+}
+
+/// The assignments from `ranges` that make `expr` equal `target`, in the
+/// same order as `assignments`. The last symbol `expr` depends on is solved
+/// for rather than searched, and if it depends on none, nothing is searched.
+fn solutions<'a>(
+    expr: &'a Linear,
+    target: i64,
+    ranges: &[RangeInclusive<i64>],
+) -> impl Iterator<Item = Vec<i64>> + 'a {
+    let solved = (0..ranges.len()).rev().find(|&i| expr.coefficient(i) != 0);
+    let mut searched = ranges.to_vec();
+    let range = solved.map(|i| std::mem::replace(&mut searched[i], 0..=0));
+    let possible = solved.is_some() || expr.as_constant() == Some(target);
+    let searched = possible
+        .then(|| assignments(searched))
+        .into_iter()
+        .flatten();
+    searched.filter_map(move |mut values| {
+        if let (Some(i), Some(range)) = (solved, &range) {
+            let rest = target.checked_sub(expr.eval(&values)?)?;
+            let c = expr.coefficient(i);
+            // Only `i64::MIN / -1` overflows, and its answer is out of range.
+            if rest.checked_rem(c)? != 0 {
+                return None;
+            }
+            let value = rest.checked_div(c)?;
+            if !range.contains(&value) {
+                return None;
+            }
+            values[i] = value;
+        }
+        if expr.eval(&values)? == target {
+            Some(values)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    /// Like day 2, with a noun and verb in cells 1 and 2: first as the
+    /// addresses of a sum that's thrown away, then as values.
+    fn day2_like(body: &str) -> Vec<i64> {
+        let source = format!(
+            "
+                    add  [0], [0], [t]
+            {}
+                    halt
+            t:      data 0
+            ",
+            body
+        );
+        assemble(&source).unwrap()
+    }
+
+    #[test]
+    fn linear_expressions() {
+        let x = Linear::symbol(0).scale(3).unwrap();
+        let y = Linear::symbol(2).add(&Linear::constant(-7)).unwrap();
+        let sum = x.add(&y).unwrap();
+        assert_eq!(sum.to_string(), "3*s0 + s2 + -7");
+        assert_eq!(sum.eval(&[2, 100, 10]), Some(9));
+        assert_eq!(
+            sum.mul(&Linear::constant(2)).unwrap().to_string(),
+            "6*s0 + 2*s2 + -14"
+        );
+        assert_eq!(sum.mul(&x), None);
+        assert_eq!(
+            sum.compare(&sum.add(&Linear::constant(1)).unwrap(), Op::LessThan),
+            Some(Linear::constant(1))
+        );
+        assert_eq!(sum.compare(&x, Op::Equals), None);
+        assert_eq!(Linear::constant(0).to_string(), "0");
+    }
+
+    #[test]
+    fn solves_linear_goals() {
+        let program = day2_like(
+            "
+                    mul  [1], #3, [t]
+                    add  [t], [2], [t]
+                    add  [t], #7, [0]
+            ",
+        );
+        let solver = Solver::new(&program)
+            .symbol(Slot::Cell(1), 0..=99)
+            .and_then(|s| s.symbol(Slot::Cell(2), 0..=99))
+            .unwrap();
+        assert_eq!(
+            solver.symbolic(Goal::Cell(0)).unwrap().to_string(),
+            "3*s0 + s1 + 7"
+        );
+        assert_eq!(
+            solver.solve(Goal::Cell(0), 3 * 12 + 34 + 7),
+            Some(Solution {
+                values: vec![0, 70],
+                fallback: None
+            })
+        );
+        assert_eq!(solver.solve(Goal::Cell(0), 3 * 99 + 99 + 8), None);
+
+        let program = assemble(
+            "
+                    in   [x]
+                    mul  [x], #2, [x]
+                    add  [x], #1, [x]
+                    out  [x]
+                    halt
+            x:      data 0
+            ",
+        )
+        .unwrap();
+        let solver = Solver::new(&program)
+            .symbol(Slot::Input, -100..=100)
+            .unwrap();
+        assert_eq!(solver.solve(Goal::Output(0), -41).unwrap().values, [-21]);
+
+        let negated = Linear::symbol(0).scale(-1).unwrap();
+        let all = [i64::MIN..=i64::MAX];
+        let found = |target| solutions(&negated, target, &all).collect::<Vec<_>>();
+        assert_eq!(found(5), [[-5]]);
+        assert_eq!(found(i64::MIN), Vec::<Vec<i64>>::new());
+
+        // A constant always or never hits, so nothing is searched.
+        let constant = Linear::constant(7);
+        let all = [i64::MIN..=i64::MAX, i64::MIN..=i64::MAX];
+        assert_eq!(solutions(&constant, 8, &all).next(), None);
+        assert_eq!(
+            solutions(&constant, 7, &all).next(),
+            Some(vec![i64::MIN; 2])
+        );
+
+        let far = Slot::Cell(MEMORY_LIMIT);
+        assert_eq!(
+            Solver::new(&program).symbol(far, 0..=1).err(),
+            Some(ErrorKind::AddressTooLarge(MEMORY_LIMIT as i64))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_brute_force() {
+        let cells = |program: &[i64]| {
+            Solver::new(program)
+                .symbol(Slot::Cell(1), 0..=20)
+                .and_then(|s| s.symbol(Slot::Cell(2), 0..=20))
+                .unwrap()
+        };
+        let solver = cells(&day2_like("mul  [1], [2], [0]"));
+        assert_eq!(solver.symbolic(Goal::Cell(0)), Err(Fallback::NotLinear));
+        assert_eq!(
+            solver.solve(Goal::Cell(0), 35),
+            Some(Solution {
+                values: vec![5, 7],
+                fallback: Some(Fallback::NotLinear)
+            })
+        );
+
+        let solver = cells(&day2_like(
+            "
+                    lt   [1], [2], [t]
+                    jt   [t], #skip
+                    add  #1, #0, [0]
+            skip:   add  [0], #0, [0]
+            ",
+        ));
+        assert_eq!(
+            solver.symbolic(Goal::Cell(0)),
+            Err(Fallback::SymbolicBranch(8))
+        );
+        assert_eq!(solver.solve(Goal::Cell(0), 1).unwrap().values, [0, 0]);
+
+        // `t` holds the sum of cells read through the symbols.
+        let solver = cells(&day2_like("add  [t], #0, [0]"));
+        assert_eq!(solver.symbolic(Goal::Cell(0)), Err(Fallback::NotLinear));
+        let solver = cells(&day2_like("arb  [1]"));
+        assert_eq!(
+            solver.symbolic(Goal::Cell(0)),
+            Err(Fallback::SymbolicAddress(4))
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Watching a `Computer` run: a hook called on every instruction, breakpoints,
+//! watchpoints, and a record of the last few instructions to explain a crash.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+use super::disasm::{Item, Operand};
+use super::{destination, operand, parse_op, Computer, ErrorKind, IntcodeError, Op, Status};
+
+/// One executed instruction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Step {
+    pub pointer: usize,
+    pub op: Op,
+    pub operands: Vec<Operand>,
+    /// What each operand resolved to: the value read, or for the operand an
+    /// instruction writes to, the address written. A jump that wasn't taken
+    /// has no value for its target.
+    pub values: Vec<i64>,
+    /// The address written to and the value written there.
+    pub write: Option<(i64, i64)>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let item = Item::Instruction {
+            op: self.op,
+            operands: self.operands.clone(),
+        };
+        let reads: Vec<String> = self
+            .values
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| self.op.output_param() != Some(i))
+            .map(|(_, v)| v.to_string())
+            .collect();
+        let mut notes = Vec::new();
+        if !reads.is_empty() {
+            notes.push(reads.join(", "));
+        }
+        if let Some((address, value)) = self.write {
+            notes.push(format!("-> [{}] = {}", address, value));
+        }
+        if notes.is_empty() {
+            write!(f, "{:>5}  {}", self.pointer, item)
+        } else {
+            let item = item.to_string();
+            write!(f, "{:>5}  {:<28} ; {}", self.pointer, item, notes.join(" "))
+        }
+    }
+}
+
+/// Why `Computer::run_traced` stopped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Stop {
+    Status(Status),
+    /// The pointer reached a breakpoint. The instruction there hasn't run.
+    Breakpoint(usize),
+    /// An instruction wrote to a watched address.
+    Watchpoint(Step),
+}
+
+type Hook<'a> = Box<dyn FnMut(&Step) + 'a>;
+
+/// What to watch for while a `Computer` runs.
+pub struct Trace<'a> {
+    hook: Option<Hook<'a>>,
+    pub breakpoints: BTreeSet<usize>,
+    pub watchpoints: BTreeSet<i64>,
+    history: VecDeque<Step>,
+    capacity: usize,
+}
+
+impl<'a> Trace<'a> {
+    /// A trace that remembers the last `capacity` instructions.
+    pub fn new(capacity: usize) -> Trace<'a> {
+        Trace {
+            hook: None,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            history: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Calls `hook` after every instruction.
+    pub fn on_step(mut self, hook: impl FnMut(&Step) + 'a) -> Trace<'a> {
+        self.hook = Some(Box::new(hook));
+        self
+    }
+
+    /// The last few instructions, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = &Step> {
+        self.history.iter()
+    }
+
+    /// Forgets the instructions run so far, keeping breakpoints and
+    /// watchpoints.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// `history`, one instruction per line.
+    pub fn dump(&self) -> String {
+        self.history.iter().map(|s| format!("{}\n", s)).collect()
+    }
+
+    fn record(&mut self, step: Step) {
+        if let Some(hook) = &mut self.hook {
+            hook(&step);
+        }
+        if self.capacity > 0 {
+            if self.history.len() == self.capacity {
+                self.history.pop_front();
+            }
+            self.history.push_back(step);
+        }
+    }
+}
+
+impl Computer {
+    /// Like `run`, but reports each instruction to `trace` and stops at its
+    /// breakpoints and watchpoints. A breakpoint at the pointer when this is
+    /// called is stepped over, so calling it again after a breakpoint carries
+    /// on. If the program fails, the trace's history shows what led up to it.
+    pub fn run_traced(&mut self, trace: &mut Trace) -> Result<Stop, IntcodeError> {
+        let mut first = true;
+        loop {
+            if !first && trace.breakpoints.contains(&self.pointer) {
+                return Ok(Stop::Breakpoint(self.pointer));
+            }
+            first = false;
+            if let Some(stop) = self.step_traced(trace)? {
+                return Ok(stop);
+            }
+        }
+    }
+
+    /// Executes one instruction, reporting it to `trace`. Ignores
+    /// breakpoints.
+    pub fn step_traced(&mut self, trace: &mut Trace) -> Result<Option<Stop>, IntcodeError> {
+        let before = self.peek().map_err(|kind| self.error(kind));
+        let status = self.step()?;
+        if status == Some(Status::NeedsInput) {
+            return Ok(Some(Stop::Status(Status::NeedsInput)));
+        }
+        let mut step = before?;
+        if let Some((address, value)) = &mut step.write {
+            *value = self.get(*address);
+        }
+        let watched = step
+            .write
+            .filter(|(address, _)| trace.watchpoints.contains(address))
+            .map(|_| step.clone());
+        trace.record(step);
+        Ok(match (watched, status) {
+            (Some(step), _) => Some(Stop::Watchpoint(step)),
+            (None, status) => status.map(Stop::Status),
+        })
+    }
+
+    /// Decodes the instruction at the pointer and resolves its operands,
+    /// without running it. Fails if running it would. A jump that won't be
+    /// taken leaves its target unresolved, as running it does.
+    fn peek(&self) -> Result<Step, ErrorKind> {
+        let pointer = self.pointer;
+        if pointer >= self.memory.len() {
+            return Err(ErrorKind::OverranMemory);
+        }
+        let (op, modes) = parse_op(self.memory.get(pointer))?;
+        let mut operands = Vec::with_capacity(op.num_params());
+        let mut values = Vec::with_capacity(op.num_params());
+        let mut write = None;
+        for (i, &mode) in modes[..op.num_params()].iter().enumerate() {
+            let value = self.memory.get(pointer + 1 + i);
+            operands.push(Operand { mode, value });
+            let skipped = match op {
+                Op::JumpIfTrue => i == 1 && values[0] == 0,
+                Op::JumpIfFalse => i == 1 && values[0] != 0,
+                _ => false,
+            };
+            if skipped {
+                continue;
+            }
+            if op.output_param() == Some(i) {
+                let address = destination(mode, value, self.relative_base)? as i64;
+                write = Some((address, 0));
+                values.push(address);
+            } else {
+                let cell = |address| self.memory.get(address);
+                values.push(operand(mode, value, self.relative_base, cell)?);
+            }
+        }
+        Ok(Step {
+            pointer,
+            op,
+            operands,
+            values,
+            write,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+
+    fn countdown() -> Vec<i64> {
+        assemble(
+            "
+                    in   [n]
+            loop:   out  [n]
+                    add  [n], #-1, [n]
+                    jt   [n], #loop
+                    halt
+            n:      data 0
+            ",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn hook_sees_every_step() {
+        let mut steps = Vec::new();
+        let mut trace = Trace::new(0).on_step(|s| steps.push(s.to_string()));
+        let mut computer = Computer::new(&[1002, 4, 3, 4, 33]);
+        assert_eq!(
+            computer.run_traced(&mut trace).unwrap(),
+            Stop::Status(Status::Halted)
+        );
+        drop(trace);
+        assert_eq!(
+            steps,
+            [
+                "    0  mul  [4], #3, [4]            ; 33, 3 -> [4] = 99",
+                "    4  halt",
+            ]
+        );
+    }
+
+    #[test]
+    fn breakpoints_and_watchpoints() {
+        let mut computer = Computer::new(&countdown());
+        computer.push_input(2);
+        let mut trace = Trace::new(16);
+        trace.breakpoints.insert(2);
+        let mut stops = Vec::new();
+        loop {
+            match computer.run_traced(&mut trace).unwrap() {
+                Stop::Status(Status::Halted) => break,
+                stop => stops.push(stop),
+            }
+        }
+        assert_eq!(
+            stops,
+            [
+                Stop::Breakpoint(2),
+                Stop::Status(Status::Output(2)),
+                Stop::Breakpoint(2),
+                Stop::Status(Status::Output(1)),
+            ]
+        );
+
+        let mut computer = Computer::new(&countdown());
+        computer.push_input(2);
+        let mut trace = Trace::new(16);
+        trace.watchpoints.insert(12);
+        match computer.run_traced(&mut trace).unwrap() {
+            Stop::Watchpoint(step) => {
+                assert_eq!(
+                    (step.pointer, step.op, step.write),
+                    (0, Op::Save, Some((12, 2)))
+                )
+            }
+            stop => panic!("{:?}", stop),
+        }
+        assert_eq!(
+            computer.run_traced(&mut trace).unwrap(),
+            Stop::Status(Status::Output(2))
+        );
+        match computer.run_traced(&mut trace).unwrap() {
+            Stop::Watchpoint(step) => assert_eq!(step.values, [2, -1, 12]),
+            stop => panic!("{:?}", stop),
+        }
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut computer = Computer::new(&countdown());
+        computer.push_input(5);
+        let mut trace = Trace::new(3);
+        while computer.run_traced(&mut trace).unwrap() != Stop::Status(Status::Halted) {}
+        let pointers: Vec<usize> = trace.history().map(|s| s.pointer).collect();
+        assert_eq!(pointers, [4, 8, 11]);
+    }
+
+    #[test]
+    fn errors_keep_history() {
+        let mut computer = Computer::new(&[1101, 1, 1, 5, 1105, 1, 100]);
+        let mut trace = Trace::new(8);
+        let error = computer.run_traced(&mut trace).unwrap_err();
+        assert_eq!(error.to_string(), "overran memory at 100 (instruction 0)");
+        let pointers: Vec<usize> = trace.history().map(|s| s.pointer).collect();
+        assert_eq!(pointers, [0, 4]);
+    }
+
+    #[test]
+    fn untaken_jumps_ignore_their_target() {
+        let mut computer = Computer::new(&[5, 3, -1, 0, 99]);
+        let mut trace = Trace::new(10);
+        assert_eq!(computer.step_traced(&mut trace).unwrap(), None);
+        assert_eq!(computer.pointer, 3);
+        assert_eq!(trace.dump(), "    0  jt   [3], [-1]               ; 0\n");
+
+        let mut computer = Computer::new(&[6, 3, -1, 0, 99]);
+        let error = computer.step_traced(&mut trace).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::NegativeAddress(-1));
+    }
+}
@@ -1,9 +1,26 @@
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<i64> = parse("inputs/1")?;
-    print_answers(1, &inputs, f1, f2);
-    Ok(())
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 1;
+
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
 }
 
 const TARGET: i64 = 2020;
@@ -1,17 +1,29 @@
-#![feature(array_windows)]
-
-use std::time::Instant;
 
 use itertools::Itertools;
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 10;
+
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<u64> = parse("inputs/10")?;
-    print_answers(10, &inputs, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 fn part1(inputs: &[u64]) -> u64 {
@@ -58,12 +70,17 @@ fn part2(inputs: &[u64]) -> u64 {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    fn part1() {
+        check_sample::<Day10>("sample1", Part::One, 220);
+    }
+
     #[test]
     fn part2() {
-        let inputs = vec![
-            28, 33, 18, 42, 31, 14, 46, 20, 48, 47, 24, 23, 49, 45, 19, 38, 39, 11, 1, 32, 25, 35,
-            8, 17, 7, 9, 4, 2, 34, 10, 3,
-        ];
-        assert_eq!(super::part2(&inputs), 19208);
+        check_sample::<Day10>("sample1", Part::Two, 19208);
     }
 }
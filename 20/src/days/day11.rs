@@ -1,22 +1,35 @@
-use std::time::Instant;
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::ops::Index;
 use std::ops::IndexMut;
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/11")?;
-    let pattern: Pattern = inputs.into();
-    print_answers(11, &pattern, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 11;
+
+    type Input = Pattern;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_lines::<String>(input)?.into())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-enum Cell {
+pub enum Cell {
     Floor,
     Empty,
     Occupied,
@@ -45,7 +58,7 @@ impl From<Vec<String>> for Pattern {
 }
 
 #[derive(Clone, Hash)]
-struct Pattern(Vec<Vec<Cell>>);
+pub struct Pattern(Vec<Vec<Cell>>);
 
 impl Index<(usize, usize)> for Pattern {
     type Output = Cell;
@@ -209,49 +222,17 @@ fn part2(pattern: &Pattern) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
     #[test]
     fn part1() {
-        let input: Vec<String> = vec![
-            "L.LL.LL.LL",
-            "LLLLLLL.LL",
-            "L.L.L..L..",
-            "LLLL.LL.LL",
-            "L.LL.LL.LL",
-            "L.LLLLL.LL",
-            "..L.L.....",
-            "LLLLLLLLLL",
-            "L.LLLLLL.L",
-            "L.LLLLL.LL",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-        let pat: super::Pattern = input.into();
-
-        assert_eq!(super::part1(&pat), 37);
+        check_sample::<Day11>("sample1", Part::One, 37);
     }
 
     #[test]
     fn part2() {
-        let input: Vec<String> = vec![
-            "L.LL.LL.LL",
-            "LLLLLLL.LL",
-            "L.L.L..L..",
-            "LLLL.LL.LL",
-            "L.LL.LL.LL",
-            "L.LLLLL.LL",
-            "..L.L.....",
-            "LLLLLLLLLL",
-            "L.LLLLLL.L",
-            "L.LLLLL.LL",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-        let pat: super::Pattern = input.into();
-
-        assert_eq!(super::part2(&pat), 26);
+        check_sample::<Day11>("sample1", Part::Two, 26);
     }
 }
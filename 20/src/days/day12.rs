@@ -1,19 +1,36 @@
-use std::time::Instant;
-
-use aoc20::util::{parse, print_answers};
-use regex::Regex;
-
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/12")?;
-    let instructions: Vec<Instruction> = inputs.iter().map(|s| s.into()).collect();
-    print_answers(12, &instructions, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use aoc_core::{
+    util::{at_column, parse_lines},
+    Solution,
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 12;
+
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     N(i32),
     S(i32),
     E(i32),
@@ -23,23 +40,29 @@ enum Instruction {
     F(i32),
 }
 
-impl From<&String> for Instruction {
-    fn from(s: &String) -> Self {
+impl FromStr for Instruction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         use Instruction::*;
 
-        let re = Regex::new(r"^([NSEWLRF])(\d+)$").unwrap();
-        let caps = re.captures(s).unwrap();
-        let n: i32 = caps[2].parse().unwrap();
-        match &caps[1] {
-            "N" => N(n),
-            "S" => S(n),
-            "E" => E(n),
-            "W" => W(n),
-            "L" => L(n),
-            "R" => R(n),
-            "F" => F(n),
-            _ => panic!("unrecognised instruction"),
-        }
+        let action = s
+            .chars()
+            .next()
+            .ok_or_else(|| anyhow!("empty instruction"))?;
+        let n: i32 = s[action.len_utf8()..]
+            .parse()
+            .map_err(|e| at_column(2, e))?;
+        Ok(match action {
+            'N' => N(n),
+            'S' => S(n),
+            'E' => E(n),
+            'W' => W(n),
+            'L' => L(n),
+            'R' => R(n),
+            'F' => F(n),
+            _ => return Err(at_column(1, anyhow!("unrecognised action {:?}", action))),
+        })
     }
 }
 
@@ -130,4 +153,21 @@ fn part2(instructions: &[Instruction]) -> i32 {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use aoc_core::util::ParseError;
+
+    /// The line and column `input` fails to parse at.
+    fn error_at(input: &str) -> (usize, Option<usize>) {
+        let err = Day12::parse(input).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        (err.line, err.column)
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_column() {
+        assert_eq!(error_at("F10\nN3\nX7\n"), (3, Some(1)));
+        assert_eq!(error_at("F10\nR9O\n"), (2, Some(2)));
+        assert_eq!(error_at("F10\n\n"), (2, None));
+    }
+}
@@ -1,16 +1,32 @@
-use std::time::Instant;
+use anyhow::ensure;
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use itertools::Itertools;
 use num::Integer;
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/13")?;
-    assert_eq!(inputs.len(), 2);
-    print_answers(13, &inputs, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 13;
+
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        let inputs: Vec<String> = parse_lines(input)?;
+        ensure!(inputs.len() == 2, "expected 2 lines, got {}", inputs.len());
+        Ok(inputs)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 fn part1(inputs: &[String]) -> u64 {
@@ -64,9 +80,17 @@ fn part2(inputs: &[String]) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    fn part1() {
+        check_sample::<Day13>("sample1", Part::One, 295);
+    }
+
     #[test]
     fn part2() {
-        let input = vec!["939".to_string(), "7,13,x,x,59,x,31,19".to_string()];
-        assert_eq!(super::part2(&input), 1068781)
+        check_sample::<Day13>("sample1", Part::Two, 1068781);
     }
 }
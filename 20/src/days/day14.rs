@@ -1,24 +1,38 @@
 use std::{
     collections::HashMap,
     str::FromStr,
-    time::Instant,
 };
 
 use bitvec::prelude::*;
 use regex::Regex;
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<Instruction> = parse("inputs/14")?;
-    print_answers(14, &inputs, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 14;
+
+    type Input = Vec<Instruction>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-enum Value {
+pub enum Value {
     Floating,
     One,
     Zero,
@@ -33,14 +47,14 @@ impl From<bool> for Value {
     }
 }
 
-enum Instruction {
+pub enum Instruction {
     Mask([Value; 36]),
     Mem { address: u64, value: u64 },
 }
 
 #[derive(thiserror::Error, Debug)]
 #[error("{msg}")]
-struct ParseError {
+pub struct ParseError {
     msg: String,
 }
 
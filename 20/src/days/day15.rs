@@ -1,15 +1,32 @@
-use std::{
-    collections::HashMap,
-    time::Instant,
-};
-
-use aoc20::util::print_answers;
-
-fn main() {
-    let now = Instant::now();
-    let inputs: Vec<usize> = "5,2,8,16,18,0,1".split(',').map(|s| s.parse().unwrap()).collect();
-    print_answers(15, &inputs, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
+use std::collections::HashMap;
+
+use aoc_core::Solution;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 15;
+
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        input
+            .trim()
+            .split(',')
+            .map(|s| Ok(s.parse()?))
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 struct Game {
@@ -69,19 +86,17 @@ fn part2(inputs: &[usize]) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
     #[test]
     fn part1() {
-        let tests: &[(&[usize], usize)] = &[
-            (&[1, 3, 2], 1),
-            // (&[2, 1, 3], 10),
-            // (&[1, 2, 3], 27),
-            // (&[2, 3, 1], 78),
-            // (&[3, 2, 1], 438),
-            // (&[3, 1, 2], 1836),
-        ];
-
-        for (inputs, expected) in tests {
-            assert_eq!(super::part1(inputs), *expected)
-        }
+        check_sample::<Day15>("sample1", Part::One, 1);
+        check_sample::<Day15>("sample2", Part::One, 10);
+        check_sample::<Day15>("sample3", Part::One, 27);
+        check_sample::<Day15>("sample4", Part::One, 78);
+        check_sample::<Day15>("sample5", Part::One, 438);
+        check_sample::<Day15>("sample6", Part::One, 1836);
     }
 }
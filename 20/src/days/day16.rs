@@ -1,4 +1,5 @@
-use aoc20::util::print_answers;
+use anyhow::anyhow;
+use aoc_core::Solution;
 use nom::{
     bytes::complete::tag,
     bytes::complete::take_until,
@@ -18,15 +19,34 @@ use std::{
     str::FromStr,
 };
 
-fn main() -> anyhow::Result<()> {
-    let lines: String = std::fs::read_to_string("inputs/16")?;
-    let input: Input = parse_input(&lines).finish().unwrap().1;
-    print_answers(16, &input, part1, part2);
-    Ok(())
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 16;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        let (_, input) = parse_input(input)
+            .finish()
+            .map_err(|e| anyhow!("{}", e))?;
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     ranges: HashMap<String, [RangeInclusive<u64>; 2]>,
     mine: Vec<u64>,
     nearby: Vec<Vec<u64>>,
@@ -61,7 +81,7 @@ fn parse_input(input: &str) -> IResult<&str, Input> {
             separated_list1(tag(","), map_res(digit1, u64::from_str)),
             many1(line_ending),
         )),
-        |(_, _, mine, _)| mine.iter().copied().collect(),
+        |(_, _, mine, _)| mine.to_vec(),
     )(input)?;
 
     let (input, nearby) = map(
@@ -73,7 +93,7 @@ fn parse_input(input: &str) -> IResult<&str, Input> {
                 separated_list1(tag(","), map_res(digit1, u64::from_str)),
             ),
         )),
-        |(_, _, v)| v.iter().map(|v| v.iter().copied().collect()).collect(),
+        |(_, _, v)| v.iter().map(|v| v.to_vec()).collect(),
     )(input)?;
 
     Ok((
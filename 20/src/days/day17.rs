@@ -4,19 +4,33 @@ use std::{
     collections::HashSet,
     fmt::{Display, Formatter, Write},
     ops::Not,
-    time::Instant,
 };
 
 use itertools::iproduct;
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/17")?;
-    print_answers(17, &inputs, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 17;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[derive(Clone, Copy, Eq, PartialEq, Hash)]
@@ -120,7 +134,7 @@ impl Board {
     }
 
     fn kill(&mut self, p: &Point) {
-        self.live.remove(&p);
+        self.live.remove(p);
         for &(x, y, z, w) in self.neighbourhood.clone().iter() {
             self.dec(p.shift(x, y, z, w))
         }
@@ -202,7 +216,7 @@ impl Display for Board {
 
             // x-origin line, repeated for each plane
             for _ in a.w..=b.w {
-                let left_pad = (a.x - 1).abs() as usize;
+                let left_pad = (a.x - 1).unsigned_abs() as usize;
                 write!(f, "{:l$}{:r$}", "", '0', l = left_pad, r = x_width - left_pad)?;
             }
             writeln!(f)?;
@@ -261,29 +275,26 @@ fn part2(inputs: &[String]) -> usize {
 
     let mut b = Board::new(&plane, &neighbours_xyzw());
 
-    for _ in 1..=3 {
-        println!("{}", b);
+    for _ in 1..=6 {
         b.step();
     }
 
-    println!("{}", b);
-
     b.live_count()
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
     #[test]
     fn part1() {
-        let input = [".#.".to_string(), "..#".to_string(), "###".to_string()];
-
-        assert_eq!(super::part1(&input), 112);
+        check_sample::<Day17>("sample1", Part::One, 112);
     }
 
     #[test]
     fn part2() {
-        let input = [".#.".to_string(), "..#".to_string(), "###".to_string()];
-
-        assert_eq!(super::part2(&input), 848);
+        check_sample::<Day17>("sample1", Part::Two, 848);
     }
 }
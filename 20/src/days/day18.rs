@@ -1,21 +1,32 @@
-#![feature(box_syntax)]
-#![feature(box_patterns)]
-
 use std::{
     ops::Not,
-    time::Instant,
     fmt::{Debug,},
     convert::TryInto,
 };
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 18;
+
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/18")?;
-    print_answers(18, &inputs, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -47,7 +58,7 @@ type Result<'a> = std::result::Result<(&'a [Token], Node), ParseError>;
 struct LeftAssoc {}
 
 impl LeftAssoc {
-    fn parse_atom(toks: &[Token]) -> Result {
+    fn parse_atom(toks: &[Token]) -> Result<'_> {
         match toks.first() {
             Some(Token::OpenParen) => {
                 let (rem, inner) = Self::parse(&toks[1..])?;
@@ -59,7 +70,7 @@ impl LeftAssoc {
         }
     }
 
-    fn parse_right(left: Node, toks: &[Token]) -> Result {
+    fn parse_right(left: Node, toks: &[Token]) -> Result<'_> {
         match toks.first() {
             Some(tok @ Token::Plus | tok @ Token::Times) => {
                 let (rem, right) = Self::parse_atom(&toks[1..])?;
@@ -80,7 +91,7 @@ impl LeftAssoc {
         }
     }
 
-    fn parse(toks: &[Token]) -> Result {
+    fn parse(toks: &[Token]) -> Result<'_> {
         let (mut rem, mut left) = Self::parse_atom(toks)?;
         loop {
             let (new_rem, node) = Self::parse_right(left, rem)?;
@@ -105,7 +116,7 @@ impl AddMult {
         }
     }
 
-    fn parse_atom(toks: &[Token]) -> Result {
+    fn parse_atom(toks: &[Token]) -> Result<'_> {
         match toks.first() {
             Some(Token::OpenParen) => {
                 let (rem, inner) = Self::parse(&toks[1..])?;
@@ -117,7 +128,7 @@ impl AddMult {
         }
     }
 
-    fn parse_expr(mut left: Node, mut toks: &[Token], prec: u8) -> Result {
+    fn parse_expr(mut left: Node, mut toks: &[Token], prec: u8) -> Result<'_> {
         while let Some(op) = toks.first().and_then(|tok| tok.try_into().ok()) {
             if Self::prec(op) < prec {
                 break;
@@ -129,7 +140,7 @@ impl AddMult {
                 if Self::prec(op2) <= Self::prec(op) {
                     break;
                 }
-                let (rem, right2) = Self::parse_expr(right, &toks, Self::prec(op2))?;
+                let (rem, right2) = Self::parse_expr(right, toks, Self::prec(op2))?;
                 toks = rem;
                 right = right2;
             }
@@ -143,7 +154,7 @@ impl AddMult {
         Ok((toks, left))
     }
 
-    fn parse(toks: &[Token]) -> Result {
+    fn parse(toks: &[Token]) -> Result<'_> {
         let (mut rem, mut left) = Self::parse_atom(toks)?;
         loop {
             let (new_rem, node) = Self::parse_expr(left, rem, 0)?;
@@ -181,9 +192,9 @@ impl From<char> for Token {
     }
 }
 
-impl Into<char> for Token {
-    fn into(self) -> char {
-        match self {
+impl From<Token> for char {
+    fn from(val: Token) -> Self {
+        match val {
             Token::OpenParen => '(',
             Token::CloseParen => ')',
             Token::Plus => '+',
@@ -221,20 +232,20 @@ impl Node {
                 Node::Op {
                     op,
                     children: (x, y),
-                } => match (x, y) {
-                    (box Node::Num { n: x }, box Node::Num { n: y }) => Node::Num {
+                } => match (*x, *y) {
+                    (Node::Num { n: x }, Node::Num { n: y }) => Node::Num {
                         n: match op {
                             Op::Add => x + y,
                             Op::Multiply => x * y,
                         },
                     },
-                    (x @ box Node::Op { .. }, y) => Node::Op {
+                    (x @ Node::Op { .. }, y) => Node::Op {
                         op,
-                        children: (Box::new(x.evaluate()), y),
+                        children: (Box::new(x.evaluate()), Box::new(y)),
                     },
-                    (x, y @ box Node::Op { .. }) => Node::Op {
+                    (x, y @ Node::Op { .. }) => Node::Op {
                         op,
-                        children: (x, Box::new(y.evaluate())),
+                        children: (Box::new(x), Box::new(y.evaluate())),
                     },
                 },
             }
@@ -245,7 +256,7 @@ impl Node {
 fn part1(inputs: &[String]) -> u64 {
     let mut acc = 0;
     for s in inputs {
-        let toks = tokens(&s);
+        let toks = tokens(s);
         let root = LeftAssoc::parse(&toks).unwrap().1;
         if let Node::Num { n } = root.evaluate() {
             acc += n;
@@ -259,7 +270,7 @@ fn part1(inputs: &[String]) -> u64 {
 fn part2(inputs: &[String]) -> u64 {
     let mut acc = 0;
     for s in inputs {
-        let toks = tokens(&s);
+        let toks = tokens(s);
         let root = AddMult::parse(&toks).unwrap().1;
         if let Node::Num { n } = root.evaluate() {
             acc += n;
@@ -272,27 +283,19 @@ fn part2(inputs: &[String]) -> u64 {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
     #[test]
     fn part1() {
-        let cases = vec![
-            (vec!["1 + 2 * 3 + 4 * 5 + 6".to_string()], 71),
-            (vec!["1 + (2 * 3) + (4 * (5 + 6))".to_string()], 51),
-        ];
-
-        for (input, expected) in cases {
-            assert_eq!(super::part1(&input), expected);
-        }
+        check_sample::<Day18>("sample1", Part::One, 71);
+        check_sample::<Day18>("sample2", Part::One, 51);
     }
 
     #[test]
     fn part2() {
-        let cases = vec![
-            (vec!["1 + 2 * 3 + 4 * 5 + 6".to_string()], 231),
-            (vec!["1 + (2 * 3) + (4 * (5 + 6))".to_string()], 51),
-        ];
-
-        for (input, expected) in cases {
-            assert_eq!(super::part2(&input), expected);
-        }
+        check_sample::<Day18>("sample1", Part::Two, 231);
+        check_sample::<Day18>("sample2", Part::Two, 51);
     }
 }
@@ -1,6 +1,6 @@
-use std::{collections::HashMap, str::FromStr, time::Instant};
+use std::{collections::HashMap, str::FromStr};
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -11,12 +11,27 @@ use nom::{
     Finish, IResult, Parser,
 };
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/19")?;
-    print_answers(19, &inputs, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 19;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 type Idx = usize;
@@ -36,7 +51,7 @@ impl Ruleset {
     fn new(inputs: &[String]) -> Ruleset {
         let mut rules = HashMap::with_capacity(inputs.len());
         for s in inputs {
-            let (key, rule) = parse_line(&s).finish().expect("parse failure").1;
+            let (key, rule) = parse_line(s).finish().expect("parse failure").1;
             rules.insert(key, rule);
         }
         Ruleset { rules }
@@ -46,19 +61,11 @@ impl Ruleset {
         let rule = &self.rules[idx];
         let res = match rule {
             Rule::Char(c) => input.strip_prefix(*c),
-            Rule::Concat(v) => v.iter().fold(Some(input), |o, idx| {
-                o.and_then(|rem| self.attempt(rem, idx))
-            }),
+            Rule::Concat(v) => v.iter().try_fold(input, |rem, idx| self.attempt(rem, idx)),
             Rule::AltConcat(u, v) => u
                 .iter()
-                .fold(Some(input), |o, idx| {
-                    o.and_then(|rem| self.attempt(rem, idx))
-                })
-                .or_else(|| {
-                    v.iter().fold(Some(input), |o, idx| {
-                        o.and_then(|rem| self.attempt(rem, idx))
-                    })
-                }),
+                .try_fold(input, |rem, idx| self.attempt(rem, idx))
+                .or_else(|| v.iter().try_fold(input, |rem, idx| self.attempt(rem, idx))),
         };
         res
     }
@@ -123,7 +130,7 @@ fn part2(inputs: &[String]) -> usize {
         let mut input = line.as_str();
 
         let mut count42 = 0;
-        while let Some(rem) = rules.attempt(&input, &42) {
+        while let Some(rem) = rules.attempt(input, &42) {
             input = rem;
             count42 += 1;
         }
@@ -145,7 +152,7 @@ fn part2(inputs: &[String]) -> usize {
             // as there are fewer 31s than 42s
             let mut count31 = 0;
             for _ in 1..n {
-                if let Some(rem) = rules.attempt(&input, &31) {
+                if let Some(rem) = rules.attempt(input, &31) {
                     count31 += 1;
                     input = rem;
                 } else {
@@ -155,7 +162,6 @@ fn part2(inputs: &[String]) -> usize {
 
             if input.is_empty() && count31 != 0 {
                 count += 1;
-                println!("PASSES with {} x 42, {} x 31: {}", n, count31, line);
                 continue 'lines;
             }
         }
@@ -165,85 +171,18 @@ fn part2(inputs: &[String]) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
     #[test]
     fn part1() {
-        let inputs: Vec<String> = vec![
-            "0: 4 1 5",
-            "1: 2 3 | 3 2",
-            "2: 4 4 | 5 5",
-            "3: 4 5 | 5 4",
-            "4: \"a\"",
-            "5: \"b\"",
-            "",
-            "ababbb",
-            "bababa",
-            "abbbab",
-            "aaabbb",
-            "aaaabbb",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-        assert_eq!(super::part1(&inputs), 2)
+        check_sample::<Day19>("sample1", Part::One, 2);
+        check_sample::<Day19>("sample2", Part::One, 3);
     }
 
     #[test]
     fn part2() {
-        let input: Vec<String> = vec![
-            "0: 8 11",
-            "1: \"a\"",
-            "2: 1 24 | 14 4",
-            "3: 5 14 | 16 1",
-            "4: 1 1",
-            "5: 1 14 | 15 1",
-            "6: 14 14 | 1 14",
-            "7: 14 5 | 1 21",
-            "8: 42",
-            "9: 14 27 | 1 26",
-            "10: 23 14 | 28 1",
-            "11: 42 31",
-            "12: 24 14 | 19 1",
-            "13: 14 3 | 1 12",
-            "14: \"b\"",
-            "15: 1 | 14",
-            "16: 15 1 | 14 14",
-            "17: 14 2 | 1 7",
-            "18: 15 15",
-            "19: 14 1 | 14 14",
-            "20: 14 14 | 1 15",
-            "21: 14 1 | 1 14",
-            "22: 14 14",
-            "23: 25 1 | 22 14",
-            "24: 14 1",
-            "25: 1 1 | 1 14",
-            "26: 14 22 | 1 20",
-            "27: 1 6 | 14 18",
-            "28: 16 1",
-            "31: 14 17 | 1 13",
-            "42: 9 14 | 10 1",
-            "",
-            "abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa",
-            "bbabbbbaabaabba",
-            "babbbbaabbbbbabbbbbbaabaaabaaa",
-            "aaabbbbbbaaaabaababaabababbabaaabbababababaaa",
-            "bbbbbbbaaaabbbbaaabbabaaa",
-            "bbbababbbbaaaaaaaabbababaaababaabab",
-            "ababaaaaaabaaab",
-            "ababaaaaabbbaba",
-            "baabbaaaabbaaaababbaababb",
-            "abbbbabbbbaaaababbbbbbaaaababb",
-            "aaaaabbaabaaaaababaa",
-            "aaaabbaaaabbaaa",
-            "aaaabbaabbaaaaaaabbbabbbaaabbaabaaa",
-            "babaaabbbaaabaababbaabababaaab",
-            "aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba",
-        ]
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
-        // assert_eq!(super::part1(&input), 3);
-        assert_eq!(super::part2(&input), 12);
+        check_sample::<Day19>("sample2", Part::Two, 12);
     }
 }
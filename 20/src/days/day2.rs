@@ -1,11 +1,28 @@
 use regex::Regex;
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<String> = parse("inputs/2")?;
-    print_answers(2, &inputs, f1, f2);
-    Ok(())
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 2;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
 }
 
 
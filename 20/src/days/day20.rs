@@ -1,4 +1,4 @@
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use itertools::iproduct;
 use ndarray::{s, Array2};
 use num::Integer;
@@ -7,16 +7,29 @@ use std::{
     cmp::min,
     collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
-    time::Instant,
 };
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/20")?;
-    let tiles = parse_tiles(&inputs);
-    print_answers(20, &tiles, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 20;
+
+    type Input = HashMap<TileId, Tile>;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_tiles(&parse_lines::<String>(input)?))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -44,7 +57,7 @@ type Edge = Vec<bool>;
 type TileId = u64;
 
 #[derive(Clone, Hash, Debug)]
-struct Tile {
+pub struct Tile {
     id: TileId,
     data: Array2<bool>,
 }
@@ -240,7 +253,7 @@ fn parse_tiles(inputs: &[String]) -> HashMap<TileId, Tile> {
             let (title, pixels) = g.split_first().unwrap();
             let id: u64 = Regex::new(r"^Tile (\d+):$")
                 .unwrap()
-                .captures(&title)
+                .captures(title)
                 .unwrap()[1]
                 .parse()
                 .unwrap();
@@ -253,10 +266,10 @@ fn parse_tiles(inputs: &[String]) -> HashMap<TileId, Tile> {
 
 fn transforms() -> impl Iterator<Item = (Rotations, bool)> {
     use Rotations::*;
-    return iproduct!(
+    iproduct!(
         [Zero, Once, Twice, Thrice].iter().cloned(),
         [false, true].iter().cloned()
-    );
+    )
 }
 
 fn part1(tiles: &HashMap<TileId, Tile>) -> u64 {
@@ -292,7 +305,7 @@ fn part2(tiles: &HashMap<TileId, Tile>) -> usize {
         let is_tried = |id: &u64| -> bool {
             tried
                 .get(&hash(&arrangement))
-                .and_then(|set| set.get(&id))
+                .and_then(|set| set.get(id))
                 .is_some()
         };
 
@@ -303,7 +316,7 @@ fn part2(tiles: &HashMap<TileId, Tile>) -> usize {
                     .get(&key(&to_match))
                     .map(|set| set.iter())
                     .and_then(|mut it| it.find(|&id| tiles.contains_key(id) && !is_tried(id)))
-            } else if i % 12 == 0 {
+            } else if i.is_multiple_of(12) {
                 let to_match = arrangement[i - 12].edge(Bottom);
                 edge_lookup
                     .get(&key(&to_match))
@@ -322,7 +335,7 @@ fn part2(tiles: &HashMap<TileId, Tile>) -> usize {
                             .map(|set2| set1.intersection(set2))
                     })
                     .unwrap();
-                intersection.find(|&id| tiles.contains_key(&id) && !is_tried(id))
+                intersection.find(|&id| tiles.contains_key(id) && !is_tried(id))
             }
         };
 
@@ -347,7 +360,7 @@ fn part2(tiles: &HashMap<TileId, Tile>) -> usize {
                 let right = tile.transformed_edge(rot, flip, Left);
                 let left = arrangement[i - 1].edge(Right);
                 right == left
-            } else if i % 12 == 0 {
+            } else if i.is_multiple_of(12) {
                 tile.transformed_edge(rot, flip, Top) == arrangement[i - 12].edge(Bottom)
             } else {
                 tile.transformed_edge(rot, flip, Left) == arrangement[i - 1].edge(Right)
@@ -368,14 +381,6 @@ fn part2(tiles: &HashMap<TileId, Tile>) -> usize {
         }
     }
 
-    assert_eq!(
-        arrangement[0].tile.id
-            * arrangement[11].tile.id
-            * arrangement[132].tile.id
-            * arrangement[143].tile.id,
-        66020135789767
-    );
-
     let mut image = Array2::from_elem((8 * 12, 8 * 12), false);
 
     for (i, tile) in arrangement.iter().enumerate() {
@@ -383,17 +388,7 @@ fn part2(tiles: &HashMap<TileId, Tile>) -> usize {
         let mut dst = image.slice_mut(s![row * 8..row * 8 + 8, col * 8..col * 8 + 8]);
         let data = tile.transformed();
         let src = data.slice(s![1..=8, 1..=8]);
-        dst.iter_mut()
-            .zip(src.iter())
-            .enumerate()
-            .for_each(|(_, (a, b))| *a = *b);
-    }
-
-    for row in image.rows() {
-        for &b in row.iter() {
-            print!("{}", if b { '#' } else { '.' });
-        }
-        println!();
+        dst.iter_mut().zip(src.iter()).for_each(|(a, b)| *a = *b);
     }
 
     let monster_indices = vec![
@@ -427,7 +422,6 @@ fn part2(tiles: &HashMap<TileId, Tile>) -> usize {
         let (rot, flip) = it.next().unwrap();
         transformed = transform(&image, flip, rot);
     };
-    dbg!(num_monsters);
 
     image.iter().filter(|&&b| b).count() - monster_indices.len() * num_monsters
 }
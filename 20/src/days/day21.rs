@@ -1,17 +1,31 @@
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use itertools::Itertools;
-use std::{collections::HashSet, time::Instant};
-
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/21")?;
-    let foods: Vec<Food> = inputs.iter().map(|l| parse_food(&l)).collect();
-    print_answers(21, &foods, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+use std::collections::HashSet;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 21;
+
+    type Input = Vec<Food>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_lines::<String>(input)?.iter().map(|l| parse_food(l)).collect())
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
-struct Food {
+pub struct Food {
     ingredients: HashSet<String>,
     allergens: HashSet<String>,
 }
@@ -87,8 +101,6 @@ fn part2(foods: &[Food]) -> String {
 
     allergen_ingreds.sort_unstable_by_key(|(_, set)| set.len());
 
-    dbg!(&allergen_ingreds);
-
     let mut assigned: HashSet<String> = HashSet::new();
     while allergen_ingreds.iter().any(|(_, set)| set.len() > 1) {
         allergen_ingreds
@@ -101,8 +113,6 @@ fn part2(foods: &[Food]) -> String {
             .for_each(|(_, set)| assigned = &assigned | set);
     }
 
-    dbg!(&allergen_ingreds);
-
     allergen_ingreds.sort_unstable_by_key(|&(a, _)| a);
 
     allergen_ingreds
@@ -1,20 +1,33 @@
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use std::{
     cmp::Ordering,
     collections::VecDeque,
     convert::TryInto,
     hash::{Hash, Hasher},
     collections::HashSet,
-    time::Instant,
 };
 
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<String> = parse("inputs/22")?;
-    let decks = parse_decks(&inputs);
-    print_answers(22, &decks, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 22;
+
+    type Input = [Deck; 2];
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_decks(&parse_lines::<String>(input)?))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 type Deck = VecDeque<usize>;
@@ -138,17 +151,16 @@ fn hash<H: Hash>(arrangement: &H) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
 
     #[test]
-    fn part2() {
-        let decks: [Deck; 2] = [
-            VecDeque::from(vec![9, 2, 6, 3, 1]),
-            VecDeque::from(vec![5, 8, 4, 7, 10]),
-        ];
-
-        let (scores, winner) = play(decks);
+    fn part1() {
+        check_sample::<Day22>("sample1", Part::One, 306);
+    }
 
-        assert_eq!(winner, 2);
-        assert_eq!(scores, [0, 291]);
+    #[test]
+    fn part2() {
+        check_sample::<Day22>("sample1", Part::Two, 291);
     }
 }
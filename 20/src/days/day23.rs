@@ -1,15 +1,31 @@
-use aoc20::util::print_answers;
-use std::{fmt::Write, time::Instant};
-
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let cups: Vec<usize> = "368195742"
-        .chars()
-        .map(|c| c.to_string().parse().unwrap())
-        .collect();
-    print_answers(23, &cups, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+use aoc_core::Solution;
+use std::fmt::Write;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 23;
+
+    type Input = Vec<usize>;
+    type Answer1 = String;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        input
+            .trim()
+            .chars()
+            .map(|c| Ok(c.to_string().parse()?))
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 struct Node {
@@ -125,23 +141,17 @@ fn part2(cups: &[usize]) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
     #[test]
     fn part1() {
-        let cups: Vec<usize> = "389125467"
-            .chars()
-            .map(|c| c.to_string().parse().unwrap())
-            .collect();
-
-        assert_eq!(super::part1(&cups), "67384529".to_string());
+        check_sample::<Day23>("sample1", Part::One, "67384529");
     }
 
     #[test]
     fn part2() {
-        let cups: Vec<usize> = "389125467"
-            .chars()
-            .map(|c| c.to_string().parse().unwrap())
-            .collect();
-
-        assert_eq!(super::part2(&cups), 149245887792);
+        check_sample::<Day23>("sample1", Part::Two, 149245887792_u64);
     }
 }
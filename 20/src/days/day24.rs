@@ -1,4 +1,5 @@
-use aoc20::util::{parse, print_answers};
+use anyhow::anyhow;
+use aoc_core::{util::parse_lines, Solution};
 use nom::{branch::alt, bytes::complete::tag, combinator::map_res, multi::many1, Finish, IResult};
 use std::{
     collections::{HashMap, HashSet},
@@ -6,14 +7,33 @@ use std::{
     str::FromStr,
 };
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<String> = parse("inputs/24")?;
-    let initial: Vec<Vec<Step>> = inputs
-        .iter()
-        .map(|s| parse_steps(s).finish().unwrap().1)
-        .collect();
-    print_answers(24, &initial, part1, part2);
-    Ok(())
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 24;
+
+    type Input = Vec<Vec<Step>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines::<String>(input)?
+            .iter()
+            .map(|s| {
+                let (_, steps) = parse_steps(s).finish().map_err(|e| anyhow!("{}", e))?;
+                Ok(steps)
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 fn parse_steps(input: &str) -> IResult<&str, Vec<Step>> {
@@ -68,7 +88,7 @@ impl Point {
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Step {
+pub enum Step {
     NorthEast,
     East,
     SouthEast,
@@ -79,7 +99,7 @@ enum Step {
 
 #[derive(thiserror::Error, Debug)]
 #[error("{0}")]
-struct ParseError(String);
+pub struct ParseError(String);
 
 impl FromStr for Step {
     type Err = ParseError;
@@ -153,7 +173,7 @@ impl Board {
     }
 
     fn kill(&mut self, p: &Point) {
-        self.live.remove(&p);
+        self.live.remove(p);
         for step in neighbours() {
             self.dec(p.shift(step))
         }
@@ -213,38 +233,16 @@ fn part2(inputs: &[Vec<Step>]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
 
     #[test]
-    fn part1() {}
+    fn part1() {
+        check_sample::<Day24>("sample1", Part::One, 10);
+    }
 
     #[test]
     fn part2() {
-        let initial: Vec<Vec<Step>> = vec![
-            "sesenwnenenewseeswwswswwnenewsewsw",
-            "neeenesenwnwwswnenewnwwsewnenwseswesw",
-            "seswneswswsenwwnwse",
-            "nwnwneseeswswnenewneswwnewseswneseene",
-            "swweswneswnenwsewnwneneseenw",
-            "eesenwseswswnenwswnwnwsewwnwsene",
-            "sewnenenenesenwsewnenwwwse",
-            "wenwwweseeeweswwwnwwe",
-            "wsweesenenewnwwnwsenewsenwwsesesenwne",
-            "neeswseenwwswnwswswnw",
-            "nenwswwsewswnenenewsenwsenwnesesenew",
-            "enewnwewneswsewnwswenweswnenwsenwsw",
-            "sweneswneswneneenwnewenewwneswswnese",
-            "swwesenesewenwneswnwwneseswwne",
-            "enesenwswwswneneswsenwnewswseenwsese",
-            "wnwnesenesenenwwnenwsewesewsesesew",
-            "nenewswnwewswnenesenwnesewesw",
-            "eneswnwswnwsenenwnwnwwseeswneewsenese",
-            "neswnwewnwnwseenwseesewsenwsweewe",
-            "wseweeenwnesenwwwswnew",
-        ]
-        .iter()
-        .map(|l| parse_steps(l).finish().unwrap().1)
-        .collect();
-
-        assert_eq!(super::part2(&initial), 2208);
+        check_sample::<Day24>("sample1", Part::Two, 2208);
     }
 }
@@ -1,9 +1,26 @@
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<u64> = parse("inputs/25")?;
-    print_answers(25, &inputs, part1, part2);
-    Ok(())
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 25;
+
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 const MODULUS: u64 = 20201227;
@@ -33,9 +50,7 @@ fn part1(inputs: &[u64]) -> u64 {
     let b_pub = inputs[1];
 
     let a_loop = find_loop_size(7, a_pub);
-    dbg!(a_loop);
     let b_loop = find_loop_size(7, b_pub);
-    dbg!(b_loop);
 
     let a_shared = transform(a_pub, b_loop);
     let b_shared = transform(b_pub, a_loop);
@@ -44,17 +59,19 @@ fn part1(inputs: &[u64]) -> u64 {
     a_shared
 }
 
-fn part2(inputs: &[u64]) -> u64 {
+fn part2(_inputs: &[u64]) -> u64 {
     todo!()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
 
     #[test]
     fn part1() {
-        assert_eq!(super::part1(&[5764801, 17807724]), 14897079)
+        check_sample::<Day25>("sample1", Part::One, 14897079);
     }
 
     #[test]
@@ -1,10 +1,26 @@
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<String> = parse("inputs/3")?;
-    let map = Map::from_inputs(&inputs);
-    print_answers(3, &map, f1, f2);
-    Ok(())
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 3;
+
+    type Input = Map;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(Map::from_inputs(&parse_lines::<String>(input)?))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
 }
 
 const WIDTH: usize = 31;
@@ -18,7 +34,7 @@ enum Space {
 type Line = [Space; WIDTH];
 type Coord = [usize; 2];
 
-struct Map {
+pub struct Map {
     data: Vec<Line>,
 }
 
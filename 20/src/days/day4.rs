@@ -1,17 +1,33 @@
 use anyhow::{anyhow, Context};
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<String> = parse("inputs/4")?;
-    let passports = parse_passports(&inputs);
-    print_answers(4, &passports, f1, f2);
-    Ok(())
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 4;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(&parse_passports(input))
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(&parse_passports(input))
+    }
 }
 
-fn parse_passports(input: &[String]) -> Vec<Passport> {
+fn parse_passports(input: &[String]) -> Vec<Passport<'_>> {
     let mut passports: Vec<Passport> = vec![];
     let mut cur: Passport = Passport(HashMap::new());
     for s in input {
@@ -42,7 +58,7 @@ lazy_static! {
 
 struct Passport<'a>(HashMap<&'a str, &'a str>);
 
-impl<'a> Passport<'_> {
+impl Passport<'_> {
     fn has_required_keys(&self) -> bool {
         REQUIRED_KEYS.iter().all(|&k| self.0.contains_key(k))
     }
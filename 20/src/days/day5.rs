@@ -1,12 +1,28 @@
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use itertools::Itertools;
 use std::ops::Range;
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<String> = parse("inputs/5")?;
-    let ids = parse_ids(&inputs);
-    print_answers(5, &ids, f1, f2);
-    Ok(())
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 5;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_ids(&parse_lines::<String>(input)?))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        f1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        f2(input)
+    }
 }
 
 const ROWS: Range<u32> = 0..127;
@@ -1,11 +1,28 @@
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use itertools::Itertools;
 use std::collections::HashSet;
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<String> = parse("inputs/6")?;
-    print_answers(6, &inputs, part1, part2);
-    Ok(())
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 6;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 /// Count number of characters appearing in any line for each group
@@ -1,6 +1,6 @@
 use std::{collections::hash_map::DefaultHasher, collections::HashSet, hash::Hasher, str::FromStr};
 
-use aoc20::util::{parse, print_answers};
+use aoc_core::{util::parse_lines, Solution};
 use bimap::BiMap;
 use nom::{
     branch::alt,
@@ -20,10 +20,27 @@ use petgraph::{
     Graph,
 };
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<String> = parse("inputs/7")?;
-    print_answers(7, &inputs, part1, part2);
-    Ok(())
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 7;
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
@@ -120,19 +137,12 @@ fn part2(inputs: &[String]) -> u64 {
             None => {
                 stack.pop();
                 acc += mul;
-                println!("ascending from {:?}", lookup.get_by_right(&cur));
             }
             Some(e) => {
                 let hash = combine_hash(hash, e.id().index());
                 traversed.insert(hash);
                 let w = *e.weight();
                 stack.push((mul * w, hash, e.target()));
-                println!(
-                    "descending from {:?} to {:?} x {}",
-                    lookup.get_by_right(&cur),
-                    lookup.get_by_right(&e.target()),
-                    w
-                );
             }
         };
     }
@@ -176,6 +186,8 @@ fn colour(input: &str) -> nom::IResult<&str, Colour> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
 
     #[test]
     fn parse_colours() {
@@ -222,36 +234,12 @@ mod tests {
 
     #[test]
     fn part1() {
-        let inputs: Vec<String> =
-            r#"light red bags contain 1 bright white bag, 2 muted yellow bags.
-dark orange bags contain 3 bright white bags, 4 muted yellow bags.
-bright white bags contain 1 shiny gold bag.
-muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
-shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
-dark olive bags contain 3 faded blue bags, 4 dotted black bags.
-vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
-faded blue bags contain no other bags.
-dotted black bags contain no other bags."#
-                .lines()
-                .map(String::from)
-                .collect();
-
-        assert_eq!(super::part1(&inputs), 4);
+        check_sample::<Day7>("sample1", Part::One, 4);
     }
 
     #[test]
     fn part2() {
-        let inputs: Vec<String> = r#"shiny gold bags contain 2 dark red bags.
-dark red bags contain 2 dark orange bags.
-dark orange bags contain 2 dark yellow bags.
-dark yellow bags contain 2 dark green bags.
-dark green bags contain 2 dark blue bags.
-dark blue bags contain 2 dark violet bags.
-dark violet bags contain no other bags."#
-            .lines()
-            .map(String::from)
-            .collect();
-
-        assert_eq!(super::part2(&inputs), 126);
+        check_sample::<Day7>("sample1", Part::Two, 32);
+        check_sample::<Day7>("sample2", Part::Two, 126);
     }
 }
@@ -1,27 +1,50 @@
-use aoc20::util::{parse, print_answers};
+use anyhow::anyhow;
+use aoc_core::{
+    util::{at_column, map_lines},
+    Solution,
+};
 use std::collections::HashSet;
 
-fn main() -> anyhow::Result<()> {
-    let inputs: Vec<String> = parse("inputs/8")?;
-    let ops: Vec<Op> = inputs.iter().map(|s| parse_op(s)).collect();
-    print_answers(8, &ops, part1, part2);
-    Ok(())
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 8;
+
+    type Input = Vec<Op>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        map_lines(input, parse_op)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
-enum Op {
+#[derive(Debug)]
+pub enum Op {
     Jmp(i32),
     Acc(i32),
     Nop(i32),
 }
 
-fn parse_op(s: &str) -> Op {
-    let (code, numstr) = s.split_once(' ').unwrap();
-    let num: i32 = numstr.parse().unwrap();
+fn parse_op(s: &str) -> anyhow::Result<Op> {
+    let (code, numstr) = s
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("expected an operation and an argument"))?;
+    let num: i32 = numstr.parse().map_err(|e| at_column(code.chars().count() + 2, e))?;
     match code {
-        "jmp" => Op::Jmp(num),
-        "acc" => Op::Acc(num),
-        "nop" => Op::Nop(num),
-        _ => panic!("unknown code {}", code),
+        "jmp" => Ok(Op::Jmp(num)),
+        "acc" => Ok(Op::Acc(num)),
+        "nop" => Ok(Op::Nop(num)),
+        _ => Err(at_column(1, anyhow!("unknown operation {:?}", code))),
     }
 }
 
@@ -101,3 +124,25 @@ fn part2(inputs: &[Op]) -> i32 {
     }
     panic!("never found answer")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::util::ParseError;
+
+    /// The line and column `input` fails to parse at.
+    fn error_at(input: &str) -> (usize, Option<usize>) {
+        let err = Day8::parse(input).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        (err.line, err.column)
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_column() {
+        assert_eq!(error_at("nop +0\nacc +x1\n"), (2, Some(5)));
+        assert_eq!(error_at("nop +0\njmp +1\nhop -1\n"), (3, Some(1)));
+        assert_eq!(error_at("nop\n"), (1, None));
+        // Columns count characters, not bytes.
+        assert_eq!(error_at("nöp +\n"), (1, Some(5)));
+    }
+}
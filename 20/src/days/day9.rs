@@ -1,14 +1,28 @@
 use itertools::Itertools;
 
-use aoc20::util::{parse, print_answers};
-use std::time::Instant;
-
-fn main() -> anyhow::Result<()> {
-    let now = Instant::now();
-    let inputs: Vec<u64> = parse("inputs/9")?;
-    print_answers(9, &inputs, part1, part2);
-    println!("Overall time: {:?}", now.elapsed());
-    Ok(())
+use aoc_core::{util::parse_lines, Solution};
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 9;
+
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 fn part1(inputs: &[u64]) -> u64 {
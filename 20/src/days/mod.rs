@@ -0,0 +1,55 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+use aoc_core::Registry;
+
+pub fn register(registry: &mut Registry) {
+    registry.add::<day1::Day1>();
+    registry.add::<day2::Day2>();
+    registry.add::<day3::Day3>();
+    registry.add::<day4::Day4>();
+    registry.add::<day5::Day5>();
+    registry.add::<day6::Day6>();
+    registry.add::<day7::Day7>();
+    registry.add::<day8::Day8>();
+    registry.add::<day9::Day9>();
+    registry.add::<day10::Day10>();
+    registry.add::<day11::Day11>();
+    registry.add::<day12::Day12>();
+    registry.add::<day13::Day13>();
+    registry.add::<day14::Day14>();
+    registry.add::<day15::Day15>();
+    registry.add::<day16::Day16>();
+    registry.add::<day17::Day17>();
+    registry.add::<day18::Day18>();
+    registry.add::<day19::Day19>();
+    registry.add::<day20::Day20>();
+    registry.add::<day21::Day21>();
+    registry.add::<day22::Day22>();
+    registry.add::<day23::Day23>();
+    registry.add::<day24::Day24>();
+    registry.add::<day25::Day25>();
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::report::Answer;
+use crate::solution::Part;
+
+/// A year's known-good answers, as recorded in its `answers.toml`:
+///
+/// ```toml
+/// [1]
+/// part1 = 365619
+/// part2 = "236873508"
+/// ```
+///
+/// Answers may be written as integers or strings; either way they're compared
+/// as the text the solution prints.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<u32, [Option<String>; 2]>,
+}
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Answers> {
+        let path = path.as_ref();
+        let contents =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        contents
+            .parse()
+            .with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.days.get(&day)?[index(part)].as_deref()
+    }
+
+    /// Compares a computed answer with the recorded one.
+    pub fn check(&self, answer: &Answer) -> Verdict {
+        match (&answer.result, self.get(answer.day, answer.part)) {
+            (_, None) => Verdict::Missing,
+            (Err(e), Some(_)) => Verdict::Error(e.clone()),
+            (Ok(actual), Some(expected)) if actual == expected => Verdict::Pass,
+            (Ok(actual), Some(expected)) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.clone(),
+            },
+        }
+    }
+}
+
+fn index(part: Part) -> usize {
+    match part {
+        Part::One => 0,
+        Part::Two => 1,
+    }
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s.parse()?;
+        let mut days = BTreeMap::new();
+        for (key, parts) in table {
+            let day: u32 = key.parse().map_err(|_| anyhow!("{:?} is not a day", key))?;
+            let parts = match parts {
+                toml::Value::Table(parts) => parts,
+                _ => bail!("day {} should be a table of parts", day),
+            };
+            let mut answers = [None, None];
+            for (name, value) in parts {
+                let part = match name.as_str() {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => bail!("day {}: unknown part {:?}", day, name),
+                };
+                answers[index(part)] = Some(match value {
+                    toml::Value::String(s) => s,
+                    toml::Value::Integer(n) => n.to_string(),
+                    other => bail!("day {} part {}: unexpected {}", day, part, other),
+                });
+            }
+            days.insert(day, answers);
+        }
+        Ok(Answers { days })
+    }
+}
+
+/// How a computed answer compares with the recorded one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// Nothing recorded for this part yet, so nothing to compare against.
+    Missing,
+    /// The solution didn't produce an answer at all.
+    Error(String),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. } | Verdict::Error(_))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Verdict::Missing => write!(f, "missing"),
+            Verdict::Error(e) => write!(f, "FAIL ({})", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn answer(day: u32, part: Part, result: Result<&str, &str>) -> Answer {
+        Answer {
+            year: 2020,
+            day,
+            part,
+            result: result.map(str::to_string).map_err(str::to_string),
+            duration: Duration::default(),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let answers: Answers = "[1]\npart1 = 42\npart2 = \"abc\"\n\n[3]\npart1 = 7\n"
+            .parse()
+            .unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("42"));
+        assert_eq!(answers.get(1, Part::Two), Some("abc"));
+        assert_eq!(answers.get(3, Part::Two), None);
+        assert_eq!(answers.get(2, Part::One), None);
+
+        assert!("[one]\npart1 = 1\n".parse::<Answers>().is_err());
+        assert!("[1]\npart3 = 1\n".parse::<Answers>().is_err());
+        assert!("[1]\npart1 = 1.5\n".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn check() {
+        let answers: Answers = "[1]\npart1 = 42\n".parse().unwrap();
+        assert_eq!(
+            answers.check(&answer(1, Part::One, Ok("42"))),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(&answer(1, Part::One, Ok("41"))),
+            Verdict::Fail {
+                expected: "42".to_string(),
+                actual: "41".to_string()
+            }
+        );
+        assert_eq!(
+            answers.check(&answer(1, Part::Two, Ok("1"))),
+            Verdict::Missing
+        );
+        assert!(answers
+            .check(&answer(1, Part::One, Err("boom")))
+            .is_failure());
+        assert_eq!(
+            answers.check(&answer(1, Part::Two, Err("boom"))),
+            Verdict::Missing
+        );
+    }
+}
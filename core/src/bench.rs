@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use crate::report::panic_message;
+use crate::solution::{DynSolution, Part};
+
+/// How many times to run each stage.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Options {
+    /// Untimed runs before measuring, to warm caches and the allocator.
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            warmup: 2,
+            iterations: 10,
+        }
+    }
+}
+
+/// One separately-timed piece of solving a day.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+/// Summary of a stage's timings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, which mustn't be empty.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort();
+        // Nearest-rank percentile.
+        let percentile = |p: f64| samples[((p * samples.len() as f64).ceil() as usize).max(1) - 1];
+        Stats {
+            iterations: samples.len(),
+            min: samples[0],
+            median: percentile(0.5),
+            p95: percentile(0.95),
+        }
+    }
+}
+
+/// Times `f` over `options.iterations` runs, after `options.warmup` untimed
+/// ones. A panic in `f` ends the measurement and is returned as the error.
+pub fn measure<T, F>(options: Options, mut f: F) -> Result<Stats, String>
+where
+    F: FnMut() -> T,
+{
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        for _ in 0..options.warmup {
+            black_box(f());
+        }
+        let samples = (0..options.iterations.max(1))
+            .map(|_| {
+                let now = Instant::now();
+                black_box(f());
+                now.elapsed()
+            })
+            .collect();
+        Stats::from_samples(samples)
+    }))
+    .map_err(panic_message)
+}
+
+/// Benchmarks each stage of `solution` against `input`. If parsing fails the
+/// parts aren't attempted.
+pub fn day(
+    solution: &dyn DynSolution,
+    input: &str,
+    options: Options,
+) -> anyhow::Result<Vec<(Stage, Result<Stats, String>)>> {
+    let parsed = solution.parse(input)?;
+    let mut results = vec![(
+        Stage::Parse,
+        measure(options, || solution.parse(input).map(drop)),
+    )];
+    for &part in &Part::ALL {
+        let stats = measure(options, || solution.solve(part, parsed.as_ref()));
+        results.push((Stage::Solve(part), stats));
+    }
+    Ok(results)
+}
+
+/// Median timings from an earlier run, to compare new runs against.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Baseline {
+    medians: BTreeMap<String, u64>,
+}
+
+impl Baseline {
+    /// Loads the baseline at `path`, or an empty one if there isn't one yet.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Baseline> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Baseline::default());
+        }
+        let contents =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let medians = serde_json::from_str(&contents)
+            .with_context(|| format!("parsing {}", path.display()))?;
+        Ok(Baseline { medians })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let contents = serde_json::to_string_pretty(&self.medians)?;
+        std::fs::write(path, contents + "\n").with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn get(&self, year: u32, day: u32, stage: Stage) -> Option<Duration> {
+        self.medians
+            .get(&key(year, day, stage))
+            .map(|&ns| Duration::from_nanos(ns))
+    }
+
+    pub fn set(&mut self, year: u32, day: u32, stage: Stage, stats: &Stats) {
+        self.medians
+            .insert(key(year, day, stage), stats.median.as_nanos() as u64);
+    }
+
+    /// How much slower `stats` is than the baseline, as a fraction (0.25 is
+    /// 25% slower), if it's slower by more than `threshold`.
+    pub fn regression(
+        &self,
+        year: u32,
+        day: u32,
+        stage: Stage,
+        stats: &Stats,
+        threshold: f64,
+    ) -> Option<f64> {
+        let before = self.get(year, day, stage)?.as_secs_f64();
+        let change = stats.median.as_secs_f64() / before - 1.0;
+        if before > 0.0 && change > threshold {
+            Some(change)
+        } else {
+            None
+        }
+    }
+}
+
+fn key(year: u32, day: u32, stage: Stage) -> String {
+    format!("{}/{}/{}", year, day, stage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::from_samples((1..=20).rev().map(ms).collect());
+        assert_eq!(stats.iterations, 20);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(10));
+        assert_eq!(stats.p95, ms(19));
+
+        let single = Stats::from_samples(vec![ms(3)]);
+        assert_eq!(
+            (single.min, single.median, single.p95),
+            (ms(3), ms(3), ms(3))
+        );
+    }
+
+    #[test]
+    fn measure_runs_warmup_and_iterations() {
+        let mut calls = 0;
+        let options = Options {
+            warmup: 3,
+            iterations: 5,
+        };
+        let stats = measure(options, || calls += 1).unwrap();
+        assert_eq!(calls, 8);
+        assert_eq!(stats.iterations, 5);
+
+        assert_eq!(
+            measure(options, || -> u32 { panic!("too slow") }),
+            Err("too slow".to_string())
+        );
+    }
+
+    #[test]
+    fn baseline_round_trip_and_regressions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        assert_eq!(Baseline::load(&path).unwrap(), Baseline::default());
+
+        let stats = |median| Stats {
+            iterations: 1,
+            min: median,
+            median,
+            p95: median,
+        };
+        let mut baseline = Baseline::default();
+        baseline.set(2020, 15, Stage::Solve(Part::Two), &stats(ms(100)));
+        baseline.save(&path).unwrap();
+
+        let baseline = Baseline::load(&path).unwrap();
+        let stage = Stage::Solve(Part::Two);
+        assert_eq!(baseline.get(2020, 15, stage), Some(ms(100)));
+        assert_eq!(
+            baseline.regression(2020, 15, stage, &stats(ms(105)), 0.1),
+            None
+        );
+        let change = baseline.regression(2020, 15, stage, &stats(ms(150)), 0.1);
+        assert!((change.unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(
+            baseline.regression(2020, 16, stage, &stats(ms(150)), 0.1),
+            None
+        );
+    }
+}
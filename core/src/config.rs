@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+
+/// Local settings, read from `aoc.toml`:
+///
+/// ```toml
+/// [inputs]
+/// # Every year's inputs, laid out as <root>/<year>/<day>.
+/// root = "/home/me/aoc-inputs"
+/// # Or just one year's, laid out as <dir>/<day>.
+/// 2019 = "../inputs-2019"
+///
+/// [fetch]
+/// # The `session` cookie from a logged-in browser.
+/// session = "53616c7465645f5f..."
+/// base_url = "https://adventofcode.com"
+/// ```
+///
+/// Relative paths are relative to the file they're written in.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Config {
+    pub input_root: Option<PathBuf>,
+    pub input_years: BTreeMap<u32, PathBuf>,
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+impl Config {
+    /// Environment variable naming the config file to use.
+    pub const ENV: &'static str = "AOC_CONFIG";
+    /// Config file picked up from the working directory if nothing else is named.
+    pub const DEFAULT_PATH: &'static str = "aoc.toml";
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Config> {
+        let path = path.as_ref();
+        let contents =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        Config::parse(&contents, base).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Loads `path` if given, otherwise the file named by `AOC_CONFIG`,
+    /// otherwise `aoc.toml` if there is one. No file at all is an empty config.
+    pub fn discover(path: Option<&Path>) -> anyhow::Result<Config> {
+        if let Some(path) = path {
+            return Config::load(path);
+        }
+        if let Some(path) = std::env::var_os(Config::ENV) {
+            return Config::load(path);
+        }
+        if Path::new(Config::DEFAULT_PATH).exists() {
+            return Config::load(Config::DEFAULT_PATH);
+        }
+        Ok(Config::default())
+    }
+
+    /// Parses a config file's contents, resolving relative paths against `base`.
+    pub fn parse(contents: &str, base: &Path) -> anyhow::Result<Config> {
+        let table: toml::Table = contents.parse()?;
+        let mut config = Config::default();
+        for (section, value) in table {
+            match (section.as_str(), value) {
+                ("inputs", toml::Value::Table(inputs)) => {
+                    for (key, value) in inputs {
+                        let path = match value {
+                            toml::Value::String(s) => base.join(s),
+                            _ => bail!("inputs.{} should be a path", key),
+                        };
+                        if key == "root" {
+                            config.input_root = Some(path);
+                        } else {
+                            let year = key.parse().map_err(|_| {
+                                anyhow!("inputs.{} is neither root nor a year", key)
+                            })?;
+                            config.input_years.insert(year, path);
+                        }
+                    }
+                }
+                ("fetch", toml::Value::Table(fetch)) => {
+                    for (key, value) in fetch {
+                        let value = match value {
+                            toml::Value::String(s) => s,
+                            _ => bail!("fetch.{} should be a string", key),
+                        };
+                        match key.as_str() {
+                            "session" => config.session = Some(value),
+                            "base_url" => config.base_url = Some(value),
+                            _ => bail!("unknown setting fetch.{}", key),
+                        }
+                    }
+                }
+                (section, _) => bail!("unknown section {:?}", section),
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let config = Config::parse(
+            "[inputs]\nroot = \"/aoc\"\n2019 = \"nineteen\"\n",
+            Path::new("/home/me"),
+        )
+        .unwrap();
+        assert_eq!(config.input_root, Some(PathBuf::from("/aoc")));
+        assert_eq!(
+            config.input_years.get(&2019),
+            Some(&PathBuf::from("/home/me/nineteen"))
+        );
+
+        assert_eq!(config.session, None);
+
+        let config = Config::parse(
+            "[fetch]\nsession = \"abc\"\nbase_url = \"http://localhost\"\n",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.base_url.as_deref(), Some("http://localhost"));
+
+        assert!(Config::parse("[inputs]\nlast = \"x\"\n", Path::new("")).is_err());
+        assert!(Config::parse("[fetch]\ncookie = \"x\"\n", Path::new("")).is_err());
+        assert!(Config::parse("[outputs]\n", Path::new("")).is_err());
+    }
+}
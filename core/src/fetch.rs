@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use anyhow::{bail, Context};
+
+use crate::config::Config;
+use crate::input::{Resolver, Variant};
+
+/// Downloads puzzle inputs for a logged-in user.
+#[derive(Clone, Debug)]
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+}
+
+/// What `Fetcher::fetch` did.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The input was already there, so nothing was downloaded.
+    Cached(PathBuf),
+}
+
+impl Fetcher {
+    /// Environment variable holding the session cookie, overriding the config.
+    pub const SESSION_ENV: &'static str = "AOC_SESSION";
+    pub const DEFAULT_BASE_URL: &'static str = "https://adventofcode.com";
+
+    pub fn new(base_url: &str, session: &str) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> anyhow::Result<Fetcher> {
+        let session = match std::env::var(Fetcher::SESSION_ENV)
+            .ok()
+            .or_else(|| config.session.clone())
+            .filter(|s| !s.trim().is_empty())
+        {
+            Some(session) => session,
+            None => bail!(
+                "no session token: set {} or fetch.session in {}",
+                Fetcher::SESSION_ENV,
+                Config::DEFAULT_PATH
+            ),
+        };
+        let base_url = config
+            .base_url
+            .as_deref()
+            .unwrap_or(Fetcher::DEFAULT_BASE_URL);
+        Ok(Fetcher::new(base_url, &session))
+    }
+
+    /// Downloads `year`'s `day` without caching it anywhere.
+    pub fn download(&self, year: u32, day: u32) -> anyhow::Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let mut response = ureq::get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", "github.com/treuherz/AdventOfCode aoc fetch")
+            .call()
+            .with_context(|| format!("fetching {}", url))?;
+        response
+            .body_mut()
+            .read_to_string()
+            .with_context(|| format!("reading {}", url))
+    }
+
+    /// Downloads `year`'s `day` to wherever `resolver` looks for it, unless
+    /// there's already a copy there.
+    pub fn fetch(&self, resolver: &Resolver, year: u32, day: u32) -> anyhow::Result<Fetched> {
+        let path = resolver
+            .path(year, day, &Variant::Real)?
+            .expect("real inputs always have a path");
+        if path.exists() {
+            return Ok(Fetched::Cached(path));
+        }
+        let input = self.download(year, day)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        std::fs::write(&path, input).with_context(|| format!("writing {}", path.display()))?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    /// Serves `input` for 2020 day 5 to requests carrying the `secret` session,
+    /// counting every request it sees.
+    fn stub_server(input: &'static str) -> (String, Arc<AtomicUsize>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::clone(&requests);
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                seen.fetch_add(1, Ordering::SeqCst);
+                let authorised = request
+                    .headers()
+                    .iter()
+                    .any(|h| h.field.equiv("Cookie") && h.value.as_str() == "session=secret");
+                let response = match (request.url(), authorised) {
+                    (_, false) => tiny_http::Response::from_string("log in").with_status_code(400),
+                    ("/2020/day/5/input", true) => tiny_http::Response::from_string(input),
+                    _ => tiny_http::Response::from_string("not found").with_status_code(404),
+                };
+                request.respond(response).unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[test]
+    fn fetch_caches_inputs() {
+        let (url, requests) = stub_server("FBFBBFFRLR\n");
+        let dir = tempfile::tempdir().unwrap();
+        let mut resolver = Resolver::new();
+        resolver.add_year(2020, dir.path().join("inputs"));
+        let path = dir.path().join("inputs/5");
+
+        let fetcher = Fetcher::new(&url, "secret");
+        assert_eq!(
+            fetcher.fetch(&resolver, 2020, 5).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "FBFBBFFRLR\n");
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        assert_eq!(
+            fetcher.fetch(&resolver, 2020, 5).unwrap(),
+            Fetched::Cached(path)
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn fetch_errors() {
+        let (url, _) = stub_server("");
+        let dir = tempfile::tempdir().unwrap();
+        let mut resolver = Resolver::new();
+        resolver.add_year(2020, dir.path());
+
+        assert!(Fetcher::new(&url, "guess")
+            .fetch(&resolver, 2020, 5)
+            .is_err());
+        assert!(Fetcher::new(&url, "secret")
+            .fetch(&resolver, 2020, 6)
+            .is_err());
+        assert!(!dir.path().join("5").exists());
+        assert!(!dir.path().join("6").exists());
+    }
+}
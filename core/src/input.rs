@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+
+use crate::config::Config;
+
+/// Which input to use for a day.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum Variant {
+    /// The personal puzzle input, `<dir>/<day>`.
+    #[default]
+    Real,
+    /// One of the puzzle text's examples, `<dir>/samples/<day>/<name>`.
+    Sample(String),
+    /// A file somewhere else entirely, or stdin if the path is `-`.
+    Custom(PathBuf),
+}
+
+/// Finds each year's input files.
+#[derive(Clone, Debug, Default)]
+pub struct Resolver {
+    /// From `AOC_INPUTS` or the command line, and used for every year.
+    root: Option<PathBuf>,
+    years: BTreeMap<u32, PathBuf>,
+    /// From the config, and used for years it doesn't list on their own.
+    config_root: Option<PathBuf>,
+}
+
+impl Resolver {
+    /// Environment variable naming a directory of inputs laid out as
+    /// `<dir>/<year>/<day>`.
+    pub const ENV: &'static str = "AOC_INPUTS";
+
+    pub fn new() -> Resolver {
+        Resolver::default()
+    }
+
+    /// Sets where `year`'s inputs live unless something overrides it.
+    pub fn add_year(&mut self, year: u32, dir: impl Into<PathBuf>) {
+        self.years.insert(year, dir.into());
+    }
+
+    /// Applies overrides, each taking precedence over the last: `config`'s
+    /// root, `config`'s per-year directories, then `AOC_INPUTS`, then `root`
+    /// (usually from a command-line flag).
+    pub fn configure(&mut self, config: &Config, root: Option<PathBuf>) {
+        if let Some(config_root) = &config.input_root {
+            self.years.clear();
+            self.config_root = Some(config_root.clone());
+        }
+        self.years.extend(config.input_years.clone());
+        self.root = root.or_else(|| std::env::var_os(Resolver::ENV).map(PathBuf::from));
+    }
+
+    /// Where to find `variant` of `year`'s `day`. Stdin has no path.
+    pub fn path(&self, year: u32, day: u32, variant: &Variant) -> anyhow::Result<Option<PathBuf>> {
+        if let Variant::Custom(path) = variant {
+            return Ok(Some(path)
+                .filter(|p| p.as_path() != Path::new("-"))
+                .cloned());
+        }
+        let dir = match (&self.root, self.years.get(&year), &self.config_root) {
+            (Some(root), _, _) => root.join(year.to_string()),
+            (None, Some(dir), _) => dir.clone(),
+            (None, None, Some(root)) => root.join(year.to_string()),
+            (None, None, None) => bail!("no input directory for {}", year),
+        };
+        Ok(Some(match variant {
+            Variant::Sample(name) => dir.join("samples").join(day.to_string()).join(name),
+            _ => dir.join(day.to_string()),
+        }))
+    }
+
+    pub fn read(&self, year: u32, day: u32, variant: &Variant) -> anyhow::Result<String> {
+        match self.path(year, day, variant)? {
+            Some(path) => std::fs::read_to_string(&path)
+                .with_context(|| format!("reading {}", path.display())),
+            None => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("reading stdin")?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        let mut resolver = Resolver::new();
+        resolver.add_year(2020, "/repo/20/inputs");
+        let path = |year, variant| resolver.path(year, 15, &variant).unwrap();
+
+        assert_eq!(
+            path(2020, Variant::Real),
+            Some(PathBuf::from("/repo/20/inputs/15"))
+        );
+        assert_eq!(
+            path(2020, Variant::Sample("sample1".to_string())),
+            Some(PathBuf::from("/repo/20/inputs/samples/15/sample1"))
+        );
+        assert_eq!(
+            path(2020, Variant::Custom(PathBuf::from("/tmp/x"))),
+            Some(PathBuf::from("/tmp/x"))
+        );
+        assert_eq!(path(2020, Variant::Custom(PathBuf::from("-"))), None);
+        assert!(resolver.path(2019, 1, &Variant::Real).is_err());
+    }
+
+    #[test]
+    fn overrides() {
+        let mut resolver = Resolver::new();
+        resolver.add_year(2019, "/repo/19/inputs");
+        resolver.add_year(2020, "/repo/20/inputs");
+        let config = Config {
+            input_years: vec![(2019, PathBuf::from("/mine/19"))]
+                .into_iter()
+                .collect(),
+            ..Config::default()
+        };
+        resolver.configure(&config, None);
+        let real = |resolver: &Resolver, year| resolver.path(year, 1, &Variant::Real).unwrap();
+        assert_eq!(real(&resolver, 2019), Some(PathBuf::from("/mine/19/1")));
+        assert_eq!(
+            real(&resolver, 2020),
+            Some(PathBuf::from("/repo/20/inputs/1"))
+        );
+
+        resolver.configure(&config, Some(PathBuf::from("/all")));
+        assert_eq!(real(&resolver, 2019), Some(PathBuf::from("/all/2019/1")));
+    }
+
+    #[test]
+    fn config_years_beat_config_root() {
+        let mut resolver = Resolver::new();
+        resolver.add_year(2019, "/repo/19/inputs");
+        resolver.add_year(2020, "/repo/20/inputs");
+        let config = Config::parse(
+            "[inputs]\nroot = \"/aoc\"\n2019 = \"nineteen\"\n",
+            Path::new("/home/me"),
+        )
+        .unwrap();
+        resolver.configure(&config, None);
+        let real = |resolver: &Resolver, year| resolver.path(year, 1, &Variant::Real).unwrap();
+        assert_eq!(
+            real(&resolver, 2019),
+            Some(PathBuf::from("/home/me/nineteen/1"))
+        );
+        assert_eq!(real(&resolver, 2020), Some(PathBuf::from("/aoc/2020/1")));
+        assert_eq!(real(&resolver, 2018), Some(PathBuf::from("/aoc/2018/1")));
+
+        resolver.configure(&config, Some(PathBuf::from("/all")));
+        assert_eq!(real(&resolver, 2019), Some(PathBuf::from("/all/2019/1")));
+    }
+}
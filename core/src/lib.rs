@@ -0,0 +1,11 @@
+pub mod answers;
+pub mod bench;
+pub mod config;
+pub mod fetch;
+pub mod input;
+pub mod report;
+pub mod sample;
+pub mod solution;
+pub mod util;
+
+pub use solution::{DynSolution, Part, Registry, Solution};
@@ -0,0 +1,265 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use serde_json::json;
+
+use crate::solution::Part;
+
+/// How answers are written out.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// The boxed listing meant for people.
+    #[default]
+    Text,
+    /// One JSON object per answer, one per line.
+    Json,
+    /// A header row, then one row per answer.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(anyhow!(
+                "unknown format {:?}, expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// The outcome of running one part of one day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub year: u32,
+    pub day: u32,
+    pub part: Part,
+    /// The answer, or why there isn't one.
+    pub result: Result<String, String>,
+    pub duration: Duration,
+}
+
+impl Answer {
+    /// Runs `f` and records how long it took. A panic inside `f` is recorded
+    /// as an error rather than unwinding any further.
+    pub fn measure<T, F>(year: u32, day: u32, part: Part, f: F) -> Answer
+    where
+        T: Display,
+        F: FnOnce() -> T,
+    {
+        let now = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        let duration = now.elapsed();
+        Answer {
+            year,
+            day,
+            part,
+            result: result.map(|a| a.to_string()).map_err(panic_message),
+            duration,
+        }
+    }
+
+    /// An answer that was never attempted, e.g. because the input was missing.
+    pub fn failed(year: u32, day: u32, part: Part, error: &anyhow::Error) -> Answer {
+        Answer {
+            year,
+            day,
+            part,
+            result: Err(format!("{:#}", error)),
+            duration: Duration::default(),
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+/// Writes a stream of answers in one format.
+pub struct Reporter<W> {
+    format: Format,
+    out: W,
+    started: bool,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, out: W) -> Reporter<W> {
+        Reporter {
+            format,
+            out,
+            started: false,
+        }
+    }
+
+    pub fn report(&mut self, answer: &Answer) -> io::Result<()> {
+        match self.format {
+            Format::Text => self.text(answer)?,
+            Format::Json => self.json(answer)?,
+            Format::Csv => self.csv(answer)?,
+        }
+        self.started = true;
+        self.out.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn text(&mut self, answer: &Answer) -> io::Result<()> {
+        writeln!(self.out, "─── Day {}, Part {} ───", answer.day, answer.part)?;
+        match &answer.result {
+            Ok(a) => writeln!(self.out, "{} (in {:?})\n", a, answer.duration),
+            Err(e) => writeln!(self.out, "error: {}\n", e),
+        }
+    }
+
+    fn json(&mut self, answer: &Answer) -> io::Result<()> {
+        let record = json!({
+            "year": answer.year,
+            "day": answer.day,
+            "part": answer.part.number(),
+            "status": status(answer),
+            "answer": answer.result.as_ref().ok(),
+            "duration_ns": answer.duration.as_nanos() as u64,
+            "error": answer.result.as_ref().err(),
+        });
+        writeln!(self.out, "{}", record)
+    }
+
+    fn csv(&mut self, answer: &Answer) -> io::Result<()> {
+        if !self.started {
+            writeln!(self.out, "year,day,part,status,answer,duration_ns,error")?;
+        }
+        let (a, e) = match &answer.result {
+            Ok(a) => (a.as_str(), ""),
+            Err(e) => ("", e.as_str()),
+        };
+        writeln!(
+            self.out,
+            "{},{},{},{},{},{},{}",
+            answer.year,
+            answer.day,
+            answer.part.number(),
+            status(answer),
+            csv_field(a),
+            answer.duration.as_nanos(),
+            csv_field(e),
+        )
+    }
+}
+
+fn status(answer: &Answer) -> &'static str {
+    if answer.result.is_ok() {
+        "ok"
+    } else {
+        "error"
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Vec<Answer> {
+        vec![
+            Answer {
+                year: 2020,
+                day: 9,
+                part: Part::One,
+                result: Ok("1234".to_string()),
+                duration: Duration::from_nanos(1500),
+            },
+            Answer {
+                year: 2020,
+                day: 9,
+                part: Part::Two,
+                result: Err("no \"answer\", sorry".to_string()),
+                duration: Duration::from_nanos(20),
+            },
+        ]
+    }
+
+    fn render(format: Format) -> String {
+        let mut reporter = Reporter::new(format, Vec::new());
+        for a in answers() {
+            reporter.report(&a).unwrap();
+        }
+        String::from_utf8(reporter.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn json() {
+        let out = render(Format::Json);
+        let lines: Vec<serde_json::Value> = out
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                json!({"year": 2020, "day": 9, "part": 1, "status": "ok", "answer": "1234",
+                       "duration_ns": 1500, "error": null}),
+                json!({"year": 2020, "day": 9, "part": 2, "status": "error", "answer": null,
+                       "duration_ns": 20, "error": "no \"answer\", sorry"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            render(Format::Csv),
+            "year,day,part,status,answer,duration_ns,error\n\
+             2020,9,1,ok,1234,1500,\n\
+             2020,9,2,error,,20,\"no \"\"answer\"\", sorry\"\n"
+        );
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            render(Format::Text),
+            "─── Day 9, Part 1 ───\n1234 (in 1.5µs)\n\n\
+             ─── Day 9, Part 2 ───\nerror: no \"answer\", sorry\n\n"
+        );
+    }
+
+    #[test]
+    fn measure_catches_panics() {
+        let ok = Answer::measure(2020, 1, Part::One, || 42);
+        assert_eq!(ok.result, Ok("42".to_string()));
+
+        let failed = Answer::measure(2020, 1, Part::Two, || -> u32 { panic!("no answer") });
+        assert_eq!(failed.result, Err("no answer".to_string()));
+    }
+
+    #[test]
+    fn format_names() {
+        assert_eq!("JSON".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use std::fmt::Display;
+
+use crate::input::{Resolver, Variant};
+use crate::solution::{Part, Solution};
+use crate::util::ParseError;
+
+/// Loads the sample called `name` for `S`'s day from `inputs` (a year's input
+/// directory), parses it exactly as the real input would be, and asserts that
+/// `part` comes out as `expected`.
+pub fn check<S: Solution>(inputs: &str, name: &str, part: Part, expected: impl Display) {
+    let mut resolver = Resolver::new();
+    resolver.add_year(S::YEAR, inputs);
+    let variant = Variant::Sample(name.to_string());
+    let input = resolver
+        .read(S::YEAR, S::DAY, &variant)
+        .and_then(|i| {
+            S::parse(&i).map_err(|e| match resolver.path(S::YEAR, S::DAY, &variant) {
+                Ok(Some(path)) => ParseError::in_file(e, &path),
+                _ => e,
+            })
+        })
+        .unwrap_or_else(|e| panic!("{} day {} {}: {:#}", S::YEAR, S::DAY, name, e));
+    let actual = match part {
+        Part::One => S::part1(&input).to_string(),
+        Part::Two => S::part2(&input).to_string(),
+    };
+    assert_eq!(
+        actual,
+        expected.to_string(),
+        "{} day {} {} part {}",
+        S::YEAR,
+        S::DAY,
+        name,
+        part
+    );
+}
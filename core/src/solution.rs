@@ -0,0 +1,176 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+
+/// A single day's puzzle: how to turn the raw input into something both parts
+/// can work from, and how to answer each part.
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Object-safe view of a `Solution`, so days with different input and answer
+/// types can sit side by side in a `Registry`.
+pub trait DynSolution {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn parse(&self, input: &str) -> anyhow::Result<Box<dyn Any>>;
+    fn solve(&self, part: Part, input: &dyn Any) -> String;
+}
+
+struct Erased<S>(PhantomData<fn() -> S>);
+
+impl<S> DynSolution for Erased<S>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn year(&self) -> u32 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> anyhow::Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, part: Part, input: &dyn Any) -> String {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input parsed by a different solution");
+        match part {
+            Part::One => S::part1(input).to_string(),
+            Part::Two => S::part2(input).to_string(),
+        }
+    }
+}
+
+/// Every known solution, keyed by year and day.
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<(u32, u32), Box<dyn DynSolution>>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    pub fn add<S>(&mut self)
+    where
+        S: Solution + 'static,
+        S::Input: 'static,
+    {
+        let previous = self
+            .solutions
+            .insert((S::YEAR, S::DAY), Box::new(Erased::<S>(PhantomData)));
+        assert!(
+            previous.is_none(),
+            "{} day {} registered twice",
+            S::YEAR,
+            S::DAY
+        );
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&dyn DynSolution> {
+        self.solutions.get(&(year, day)).map(|s| s.as_ref())
+    }
+
+    /// All of `year`'s solutions, in day order.
+    pub fn year(&self, year: u32) -> impl Iterator<Item = &dyn DynSolution> {
+        self.solutions
+            .range((year, 0)..=(year, u32::MAX))
+            .map(|(_, s)| s.as_ref())
+    }
+
+    pub fn years(&self) -> Vec<u32> {
+        let mut years: Vec<u32> = self.solutions.keys().map(|&(y, _)| y).collect();
+        years.dedup();
+        years
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse_lines;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u32 = 2000;
+        const DAY: u32 = 1;
+
+        type Input = Vec<i64>;
+        type Answer1 = i64;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> anyhow::Result<Self::Input> {
+            parse_lines(input)
+        }
+
+        fn part1(input: &Self::Input) -> i64 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> usize {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn solution() {
+        let input = Sum::parse("1\n2\n3\n").unwrap();
+        assert_eq!(Sum::part1(&input), 6);
+        assert_eq!(Sum::part2(&input), 3);
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = Registry::new();
+        registry.add::<Sum>();
+
+        assert!(registry.get(2000, 2).is_none());
+        let sum = registry.get(2000, 1).unwrap();
+        let input = sum.parse("1\n2\n3\n").unwrap();
+        assert_eq!(sum.solve(Part::One, input.as_ref()), "6");
+        assert_eq!(sum.solve(Part::Two, input.as_ref()), "3");
+        assert_eq!(registry.years(), vec![2000]);
+        assert_eq!(registry.year(2000).count(), 1);
+    }
+}
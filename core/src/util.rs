@@ -0,0 +1,222 @@
+use anyhow::Context;
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How `parse` treats lines that don't parse as the requested type.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Strictness {
+    /// Fail on the first line that doesn't parse.
+    #[default]
+    Strict,
+    /// Silently drop lines that don't parse.
+    Lenient,
+}
+
+/// A line of input that couldn't be parsed. Displays as a diagnostic pointing
+/// at the offending text:
+///
+/// ```text
+/// invalid digit found in string
+///  --> inputs/12:3:2
+///   |
+/// 3 | F1x0
+///   |  ^^^
+/// ```
+#[derive(Debug)]
+pub struct ParseError {
+    /// The file the input came from, if it came from a file.
+    pub path: Option<PathBuf>,
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, if the parser knew where on the line things went wrong.
+    pub column: Option<usize>,
+    pub text: String,
+    pub error: anyhow::Error,
+}
+
+impl ParseError {
+    pub fn new(line: usize, text: &str, error: anyhow::Error) -> ParseError {
+        let (column, error) = match error.downcast::<ColumnError>() {
+            Ok(e) => (Some(e.column), e.error),
+            Err(e) => (None, e),
+        };
+        ParseError {
+            path: None,
+            line,
+            column,
+            text: text.to_string(),
+            error,
+        }
+    }
+
+    /// Records `path` in `error` if it's a `ParseError`.
+    pub fn in_file(mut error: anyhow::Error, path: &Path) -> anyhow::Error {
+        if let Some(e) = error.downcast_mut::<ParseError>() {
+            e.path = Some(path.to_path_buf());
+        }
+        error
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "<input>".to_string(),
+        };
+        let column = self.column.unwrap_or(1);
+        let gutter = " ".repeat(self.line.to_string().len());
+        let width = self.text.chars().count();
+        let (indent, carets) = match self.column {
+            Some(c) => (c - 1, width.saturating_sub(c - 1).max(1)),
+            None => (0, width.max(1)),
+        };
+        writeln!(f, "{:#}", self.error)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, path, self.line, column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(indent),
+            "^".repeat(carets)
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An error from parsing part of a line, and the 1-based column it starts at.
+/// Line parsers return this (via `at_column`) so that `ParseError` can point
+/// at the right place.
+#[derive(Debug)]
+pub struct ColumnError {
+    pub column: usize,
+    pub error: anyhow::Error,
+}
+
+impl Display for ColumnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {:#}", self.column, self.error)
+    }
+}
+
+impl std::error::Error for ColumnError {}
+
+pub fn at_column(column: usize, error: impl Into<anyhow::Error>) -> anyhow::Error {
+    ColumnError {
+        column,
+        error: error.into(),
+    }
+    .into()
+}
+
+/// Reads `path` and parses each line as a `T`, failing on the first bad line.
+pub fn parse<T>(path: &str) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<anyhow::Error>,
+{
+    parse_with(path, Strictness::Strict)
+}
+
+pub fn parse_with<T>(path: &str, strictness: Strictness) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<anyhow::Error>,
+{
+    let contents = std::fs::read_to_string(path).with_context(|| format!("reading {}", path))?;
+    parse_lines_with(&contents, strictness).map_err(|e| ParseError::in_file(e, Path::new(path)))
+}
+
+/// Parses each line of `contents` as a `T`, failing on the first bad line.
+pub fn parse_lines<T>(contents: &str) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<anyhow::Error>,
+{
+    parse_lines_with(contents, Strictness::Strict)
+}
+
+pub fn parse_lines_with<T>(contents: &str, strictness: Strictness) -> anyhow::Result<Vec<T>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<anyhow::Error>,
+{
+    match strictness {
+        Strictness::Strict => map_lines(contents, |l| l.parse::<T>().map_err(Into::into)),
+        Strictness::Lenient => Ok(contents.lines().filter_map(|l| l.parse().ok()).collect()),
+    }
+}
+
+/// Parses each line of `contents` with `f`, failing with a `ParseError` on the
+/// first bad line.
+pub fn map_lines<T, F>(contents: &str, f: F) -> anyhow::Result<Vec<T>>
+where
+    F: Fn(&str) -> anyhow::Result<T>,
+{
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, l)| f(l).map_err(|e| ParseError::new(i + 1, l, e).into()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_parse_fails_on_bad_line() {
+        let res: anyhow::Result<Vec<i64>> = parse_lines("1\ntwo\n3\n");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_line() {
+        let err = parse_lines::<i64>("1\ntwo\n3\n").unwrap_err();
+        let err = ParseError::in_file(err, Path::new("inputs/1"));
+        let parse_error = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(parse_error.line, 2);
+        assert_eq!(parse_error.text, "two");
+        assert_eq!(
+            err.to_string(),
+            "invalid digit found in string\n \
+             --> inputs/1:2:1\n  \
+             |\n\
+             2 | two\n  \
+             | ^^^"
+        );
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_column() {
+        let parse = |l: &str| -> anyhow::Result<i64> {
+            let n = l
+                .strip_prefix('+')
+                .ok_or_else(|| at_column(1, anyhow::anyhow!("no sign")))?;
+            n.parse().map_err(|e| at_column(2, e))
+        };
+        assert_eq!(map_lines("+1\n+2\n", parse).unwrap(), vec![1, 2]);
+
+        let err = map_lines("+1\n+1x0\n", parse).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid digit found in string\n \
+             --> <input>:2:2\n  \
+             |\n\
+             2 | +1x0\n  \
+             |  ^^^"
+        );
+        let err = map_lines("-1\n", parse).unwrap_err();
+        assert!(err.to_string().starts_with("no sign\n --> <input>:1:1\n"));
+    }
+
+    #[test]
+    fn lenient_parse_drops_bad_lines() {
+        let res: Vec<i64> = parse_lines_with("1\ntwo\n3\n", Strictness::Lenient).unwrap();
+        assert_eq!(res, vec![1, 3]);
+    }
+}
@@ -0,0 +1,478 @@
+use std::any::Any;
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::bail;
+use clap::{Parser, Subcommand};
+
+use aoc_core::answers::{Answers, Verdict};
+use aoc_core::bench::{self, Baseline};
+use aoc_core::config::Config;
+use aoc_core::fetch::{Fetched, Fetcher};
+use aoc_core::input::{Resolver, Variant};
+use aoc_core::report::{Answer, Format, Reporter};
+use aoc_core::util::ParseError;
+use aoc_core::{DynSolution, Part, Registry};
+
+mod scaffold;
+
+/// Runs Advent of Code solutions from any year.
+#[derive(Parser)]
+#[command(
+    name = "aoc",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: Args,
+    /// Directory of inputs laid out as `<dir>/<year>/<day>` [env: AOC_INPUTS].
+    #[arg(long, global = true)]
+    inputs: Option<PathBuf>,
+    /// Config file to read instead of `aoc.toml` [env: AOC_CONFIG].
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Download missing puzzle inputs, using the session token from
+    /// `AOC_SESSION` or `fetch.session` in the config.
+    Fetch {
+        /// Puzzle year, e.g. 2020 or 20.
+        year: u32,
+        /// A single day, an inclusive range like `3..7` or `3-7`, or `all`.
+        #[arg(default_value = "all")]
+        days: Days,
+    },
+    /// Generate a new day's module, register it, and create empty input and
+    /// sample files.
+    NewDay {
+        /// Puzzle year, e.g. 2020 or 20.
+        year: u32,
+        day: u32,
+    },
+}
+
+#[derive(clap::Args)]
+struct Args {
+    /// Puzzle year, e.g. 2020 or 20.
+    #[arg(required = true)]
+    year: Option<u32>,
+    /// A single day, an inclusive range like `3..7` or `3-7`, or `all`.
+    #[arg(default_value = "all")]
+    days: Days,
+    /// Use the named example from the puzzle text instead of the real input.
+    #[arg(long, conflicts_with = "input")]
+    sample: Option<String>,
+    /// Read the input from this file instead, or from stdin if it's `-`.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// How to write answers: text, json (one object per line) or csv.
+    #[arg(long, env = "AOC_FORMAT", default_value = "text")]
+    format: Format,
+    /// Check answers against the year's `answers.toml` instead of printing them.
+    #[arg(long, conflicts_with = "bench")]
+    verify: bool,
+    /// Time parsing and each part separately instead of printing answers.
+    #[arg(long)]
+    bench: bool,
+    /// Timed runs of each stage when benchmarking.
+    #[arg(long, default_value_t = bench::Options::default().iterations, requires = "bench")]
+    iterations: usize,
+    /// Untimed runs of each stage before measuring.
+    #[arg(long, default_value_t = bench::Options::default().warmup, requires = "bench")]
+    warmup: usize,
+    /// Earlier medians to compare benchmarks against.
+    #[arg(long, default_value = ".aoc-bench.json", requires = "bench")]
+    baseline: PathBuf,
+    /// Record this run's medians in the baseline file.
+    #[arg(long, requires = "bench")]
+    save_baseline: bool,
+    /// How much slower than the baseline counts as a regression, as a percentage.
+    #[arg(long, default_value_t = 10.0, requires = "bench")]
+    threshold: f64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Days {
+    All,
+    Range(u32, u32),
+}
+
+impl Days {
+    fn contains(&self, day: u32) -> bool {
+        self.range().contains(&day)
+    }
+
+    fn range(&self) -> RangeInclusive<u32> {
+        match *self {
+            Days::All => 1..=25,
+            Days::Range(first, last) => first..=last,
+        }
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = |d: &str| {
+            d.trim()
+                .parse::<u32>()
+                .map_err(|_| format!("{:?} is not a day", d))
+        };
+
+        if s == "all" {
+            return Ok(Days::All);
+        }
+        let split = s.split_once("..").or_else(|| s.split_once('-'));
+        let (first, last) = match split {
+            Some((first, last)) => (day(first)?, day(last)?),
+            None => (day(s)?, day(s)?),
+        };
+        if first > last {
+            return Err(format!("{} is an empty range", s));
+        }
+        Ok(Days::Range(first, last))
+    }
+}
+
+impl fmt::Display for Days {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Days::All => write!(f, "all"),
+            Days::Range(first, last) if first == last => write!(f, "{}", first),
+            Days::Range(first, last) => write!(f, "{}..{}", first, last),
+        }
+    }
+}
+
+fn registry() -> Registry {
+    let mut registry = Registry::new();
+    aoc18::days::register(&mut registry);
+    aoc19::days::register(&mut registry);
+    aoc20::days::register(&mut registry);
+    registry
+}
+
+fn resolver(config: &Config, root: Option<PathBuf>) -> Resolver {
+    let mut resolver = Resolver::new();
+    resolver.add_year(2018, aoc18::INPUTS);
+    resolver.add_year(2019, aoc19::INPUTS);
+    resolver.add_year(2020, aoc20::INPUTS);
+    resolver.configure(config, root);
+    resolver
+}
+
+/// The crate holding `year`'s solutions, found from where its inputs live.
+fn crate_dir(year: u32) -> Option<&'static Path> {
+    let inputs = match year {
+        2018 => aoc18::INPUTS,
+        2019 => aoc19::INPUTS,
+        2020 => aoc20::INPUTS,
+        _ => return None,
+    };
+    Path::new(inputs).parent()
+}
+
+fn answers_file(year: u32) -> Option<&'static str> {
+    match year {
+        2018 => Some(aoc18::ANSWERS),
+        2019 => Some(aoc19::ANSWERS),
+        2020 => Some(aoc20::ANSWERS),
+        _ => None,
+    }
+}
+
+/// Where to read each day's input from.
+struct Inputs {
+    resolver: Resolver,
+    variant: Variant,
+}
+
+impl Inputs {
+    fn read(&self, solution: &dyn DynSolution) -> anyhow::Result<String> {
+        self.resolver
+            .read(solution.year(), solution.day(), &self.variant)
+    }
+
+    /// Points a `ParseError` in `error` at the file the input came from.
+    fn locate(&self, solution: &dyn DynSolution, error: anyhow::Error) -> anyhow::Error {
+        match self
+            .resolver
+            .path(solution.year(), solution.day(), &self.variant)
+        {
+            Ok(Some(path)) => ParseError::in_file(error, &path),
+            _ => error,
+        }
+    }
+
+    fn parse(&self, solution: &dyn DynSolution) -> anyhow::Result<Box<dyn Any>> {
+        let input = self.read(solution)?;
+        solution.parse(&input).map_err(|e| self.locate(solution, e))
+    }
+}
+
+fn solve(solution: &dyn DynSolution, inputs: &Inputs) -> Vec<Answer> {
+    let (year, day) = (solution.year(), solution.day());
+    match inputs.parse(solution) {
+        Ok(input) => Part::ALL
+            .iter()
+            .map(|&part| Answer::measure(year, day, part, || solution.solve(part, input.as_ref())))
+            .collect(),
+        Err(e) => Part::ALL
+            .iter()
+            .map(|&part| Answer::failed(year, day, part, &e))
+            .collect(),
+    }
+}
+
+/// Solves both parts of `solution`, reporting each. Returns whether both
+/// produced an answer.
+fn run<W: Write>(
+    solution: &dyn DynSolution,
+    inputs: &Inputs,
+    reporter: &mut Reporter<W>,
+) -> io::Result<bool> {
+    let answers = solve(solution, inputs);
+    for answer in &answers {
+        reporter.report(answer)?;
+    }
+    Ok(answers.iter().all(|a| a.result.is_ok()))
+}
+
+/// Solves both parts of `solution` and prints how each compares with the
+/// recorded answers.
+fn verify(solution: &dyn DynSolution, inputs: &Inputs, answers: &Answers) -> Vec<Verdict> {
+    let verdicts: Vec<Verdict> = solve(solution, inputs)
+        .iter()
+        .map(|a| answers.check(a))
+        .collect();
+    println!(
+        "Day {:>2}: part 1 {}, part 2 {}",
+        solution.day(),
+        verdicts[0],
+        verdicts[1]
+    );
+    verdicts
+}
+
+fn verify_all(year: u32, solutions: &[&dyn DynSolution], inputs: &Inputs) -> anyhow::Result<()> {
+    let path = match answers_file(year) {
+        Some(path) => path,
+        None => bail!("no answers file for {}", year),
+    };
+    let answers = Answers::load(path)?;
+    let verdicts: Vec<Verdict> = solutions
+        .iter()
+        .flat_map(|&s| verify(s, inputs, &answers))
+        .collect();
+    let count = |f: fn(&Verdict) -> bool| verdicts.iter().filter(|v| f(v)).count();
+    let failed = count(Verdict::is_failure);
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        count(|v| *v == Verdict::Pass),
+        failed,
+        count(|v| *v == Verdict::Missing)
+    );
+    if failed > 0 {
+        bail!("{} answer(s) failed verification", failed);
+    }
+    Ok(())
+}
+
+fn bench_all(args: &Args, solutions: &[&dyn DynSolution], inputs: &Inputs) -> anyhow::Result<()> {
+    let options = bench::Options {
+        warmup: args.warmup,
+        iterations: args.iterations,
+    };
+    let mut baseline = Baseline::load(&args.baseline)?;
+    let mut regressions = 0;
+    for &solution in solutions {
+        let (year, day) = (solution.year(), solution.day());
+        let results = match inputs
+            .read(solution)
+            .and_then(|i| bench::day(solution, &i, options))
+            .map_err(|e| inputs.locate(solution, e))
+        {
+            Ok(results) => results,
+            Err(e) => {
+                println!("Day {:>2} {:#}", day, e);
+                continue;
+            }
+        };
+        for (stage, stats) in results {
+            let stats = match stats {
+                Ok(stats) => stats,
+                Err(e) => {
+                    println!("Day {:>2} {:<5}  error: {}", day, stage, e);
+                    continue;
+                }
+            };
+            print!(
+                "Day {:>2} {:<5}  min {:>12}  median {:>12}  p95 {:>12}",
+                day,
+                stage,
+                format!("{:?}", stats.min),
+                format!("{:?}", stats.median),
+                format!("{:?}", stats.p95),
+            );
+            match baseline.regression(year, day, stage, &stats, args.threshold / 100.0) {
+                Some(change) => {
+                    regressions += 1;
+                    println!(
+                        "  REGRESSED +{:.0}% (was {:?})",
+                        change * 100.0,
+                        baseline.get(year, day, stage).unwrap()
+                    );
+                }
+                None => println!(),
+            }
+            if args.save_baseline {
+                baseline.set(year, day, stage, &stats);
+            }
+        }
+    }
+    if args.save_baseline {
+        baseline.save(&args.baseline)?;
+    }
+    if regressions > 0 {
+        bail!("{} stage(s) regressed", regressions);
+    }
+    Ok(())
+}
+
+/// Accepts years with or without the century.
+fn full_year(year: u32) -> u32 {
+    if year < 100 {
+        year + 2000
+    } else {
+        year
+    }
+}
+
+fn fetch(year: u32, days: Days, resolver: &Resolver, config: &Config) -> anyhow::Result<()> {
+    let fetcher = Fetcher::from_config(config)?;
+    let mut failed = 0;
+    for day in days.range() {
+        match fetcher.fetch(resolver, year, day) {
+            Ok(Fetched::Downloaded(path)) => println!("Day {}: saved {}", day, path.display()),
+            Ok(Fetched::Cached(path)) => {
+                println!(
+                    "Day {}: already have {}, not downloading",
+                    day,
+                    path.display()
+                )
+            }
+            Err(e) => {
+                eprintln!("Day {}: {:#}", day, e);
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{} day(s) failed", failed);
+    }
+    Ok(())
+}
+
+fn new_day(year: u32, day: u32) -> anyhow::Result<()> {
+    let dir = match crate_dir(year) {
+        Some(dir) => dir,
+        None => bail!("no crate for {}", year),
+    };
+    for path in scaffold::new_day(dir, year, day)? {
+        println!("created {}", path.display());
+    }
+    Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let config = Config::discover(cli.config.as_deref())?;
+    let resolver = resolver(&config, cli.inputs.clone());
+
+    match cli.command {
+        Some(Command::Fetch { year, days }) => fetch(full_year(year), days, &resolver, &config),
+        Some(Command::NewDay { year, day }) => new_day(full_year(year), day),
+        None => run_all(&cli.run, resolver),
+    }
+}
+
+fn run_all(args: &Args, resolver: Resolver) -> anyhow::Result<()> {
+    let year = full_year(args.year.expect("clap requires a year"));
+
+    let registry = registry();
+    let solutions: Vec<&dyn DynSolution> = registry
+        .year(year)
+        .filter(|s| args.days.contains(s.day()))
+        .collect();
+    if solutions.is_empty() {
+        bail!("no solutions for {} matching {}", year, args.days);
+    }
+
+    let variant = match (&args.sample, &args.input) {
+        (Some(name), _) => Variant::Sample(name.clone()),
+        (None, Some(path)) => Variant::Custom(path.clone()),
+        (None, None) => Variant::Real,
+    };
+    if matches!(variant, Variant::Custom(_)) && solutions.len() > 1 {
+        bail!("--input can only be used with a single day");
+    }
+    let inputs = Inputs { resolver, variant };
+
+    if args.verify {
+        return verify_all(year, &solutions, &inputs);
+    }
+    if args.bench {
+        return bench_all(args, &solutions, &inputs);
+    }
+
+    let mut reporter = Reporter::new(args.format, io::stdout().lock());
+    let mut failed = 0;
+    for solution in solutions {
+        if !run(solution, &inputs, &mut reporter)? {
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        bail!("{} day(s) failed", failed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days() {
+        assert_eq!("all".parse(), Ok(Days::All));
+        assert_eq!("17".parse(), Ok(Days::Range(17, 17)));
+        assert_eq!("3..7".parse(), Ok(Days::Range(3, 7)));
+        assert_eq!("3-7".parse(), Ok(Days::Range(3, 7)));
+        assert!("7..3".parse::<Days>().is_err());
+        assert!("seven".parse::<Days>().is_err());
+    }
+
+    #[test]
+    fn registry_covers_every_year() {
+        let registry = registry();
+        assert_eq!(registry.years(), vec![2018, 2019, 2020]);
+        assert_eq!(registry.year(2020).count(), 25);
+        let resolver = resolver(&Config::default(), None);
+        for year in registry.years() {
+            for solution in registry.year(year) {
+                let path = resolver.path(year, solution.day(), &Variant::Real);
+                assert!(path.unwrap().unwrap().exists());
+            }
+            assert!(Answers::load(answers_file(year).unwrap()).is_ok());
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Generates the boilerplate for a new day in one of the year crates.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+
+const TEMPLATE: &str = r#"use aoc_core::{util::parse_lines, Solution};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const YEAR: u32 = {year};
+    const DAY: u32 = {day};
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
+fn part1(_inputs: &[String]) -> usize {
+    todo!()
+}
+
+fn part2(_inputs: &[String]) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_sample;
+    use aoc_core::Part;
+
+    #[test]
+    #[ignore = "needs inputs/samples/{day}/sample1 and its answer"]
+    fn part1() {
+        check_sample::<Day{day}>("sample1", Part::One, 0);
+    }
+
+    #[test]
+    #[ignore = "needs inputs/samples/{day}/sample1 and its answer"]
+    fn part2() {
+        check_sample::<Day{day}>("sample1", Part::Two, 0);
+    }
+}
+"#;
+
+/// Adds `day` to the year crate at `crate_dir`: a module from the template,
+/// its entries in `days/mod.rs`, and empty input and sample files. Refuses
+/// to touch a day that already exists. Returns the files it created.
+pub fn new_day(crate_dir: &Path, year: u32, day: u32) -> anyhow::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        bail!("{} is not a day of Advent", day);
+    }
+    let days = crate_dir.join("src/days");
+    let module = days.join(format!("day{}.rs", day));
+    let mod_rs = days.join("mod.rs");
+    let registry =
+        fs::read_to_string(&mod_rs).with_context(|| format!("reading {}", mod_rs.display()))?;
+    if module.exists() || registry.contains(&format!("pub mod day{};", day)) {
+        bail!("{} day {} already exists", year, day);
+    }
+    let registry =
+        register(&registry, day).with_context(|| format!("updating {}", mod_rs.display()))?;
+
+    let source = TEMPLATE
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string());
+    write(&module, &source)?;
+    fs::write(&mod_rs, registry).with_context(|| format!("writing {}", mod_rs.display()))?;
+
+    let mut created = vec![module];
+    let inputs = crate_dir.join("inputs");
+    for path in [
+        inputs.join(day.to_string()),
+        inputs.join("samples").join(day.to_string()).join("sample1"),
+    ] {
+        if !path.exists() {
+            write(&path, "")?;
+            created.push(path);
+        }
+    }
+    Ok(created)
+}
+
+fn write(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("writing {}", path.display()))
+}
+
+/// Adds `day`'s `pub mod` and `registry.add` lines to a `days/mod.rs`,
+/// keeping both in day order.
+fn register(mod_rs: &str, day: u32) -> anyhow::Result<String> {
+    let mut lines: Vec<String> = mod_rs.lines().map(String::from).collect();
+    insert_in_order(&mut lines, &format!("pub mod day{};", day), day, |l| {
+        l.strip_prefix("pub mod day")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    })
+    .ok_or_else(|| anyhow::anyhow!("no `pub mod dayN;` declarations"))?;
+    insert_in_order(
+        &mut lines,
+        &format!("    registry.add::<day{0}::Day{0}>();", day),
+        day,
+        |l| {
+            let rest = l.trim_start().strip_prefix("registry.add::<day")?;
+            rest[..rest.find("::")?].parse().ok()
+        },
+    )
+    .ok_or_else(|| anyhow::anyhow!("no `registry.add` calls"))?;
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Inserts `line` after the last line `day_of` numbers below `day`, or before
+/// the first numbered line if there isn't one. `None` if no lines are numbered.
+fn insert_in_order<F>(lines: &mut Vec<String>, line: &str, day: u32, day_of: F) -> Option<()>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let numbered: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, day_of(l)?)))
+        .collect();
+    let at = match numbered.iter().rfind(|&&(_, d)| d < day) {
+        Some(&(i, _)) => i + 1,
+        None => numbered.first()?.0,
+    };
+    lines.insert(at, line.to_string());
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "pub mod day1;\npub mod day3;\n\nuse aoc_core::Registry;\n\n\
+        pub fn register(registry: &mut Registry) {\n    \
+        registry.add::<day1::Day1>();\n    registry.add::<day3::Day3>();\n}\n";
+
+    #[test]
+    fn registers_in_day_order() {
+        assert_eq!(
+            register(MOD_RS, 2).unwrap(),
+            "pub mod day1;\npub mod day2;\npub mod day3;\n\nuse aoc_core::Registry;\n\n\
+             pub fn register(registry: &mut Registry) {\n    \
+             registry.add::<day1::Day1>();\n    registry.add::<day2::Day2>();\n    \
+             registry.add::<day3::Day3>();\n}\n"
+        );
+        let first = register(MOD_RS, 4).unwrap();
+        assert!(first.contains("pub mod day3;\npub mod day4;\n"));
+        assert!(first.contains("<day3::Day3>();\n    registry.add::<day4::Day4>();\n}"));
+    }
+
+    #[test]
+    fn new_day_refuses_to_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/days")).unwrap();
+        fs::write(dir.path().join("src/days/mod.rs"), MOD_RS).unwrap();
+        fs::create_dir_all(dir.path().join("inputs")).unwrap();
+        fs::write(dir.path().join("inputs/2"), "already fetched\n").unwrap();
+
+        let created = new_day(dir.path(), 2020, 2).unwrap();
+        assert_eq!(
+            created,
+            vec![
+                dir.path().join("src/days/day2.rs"),
+                dir.path().join("inputs/samples/2/sample1"),
+            ]
+        );
+        let module = fs::read_to_string(dir.path().join("src/days/day2.rs")).unwrap();
+        assert!(module.contains("pub struct Day2;"));
+        assert!(module.contains("const YEAR: u32 = 2020;"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("inputs/2")).unwrap(),
+            "already fetched\n"
+        );
+
+        assert!(new_day(dir.path(), 2020, 2).is_err());
+        assert!(new_day(dir.path(), 2020, 3).is_err());
+        assert!(new_day(dir.path(), 2020, 26).is_err());
+        assert_eq!(
+            module,
+            fs::read_to_string(dir.path().join("src/days/day2.rs")).unwrap()
+        );
+    }
+}